@@ -0,0 +1,76 @@
+//! Per-call overhead of preparing a request, parsing the metadata on each call
+//! compared with the templates and config precompiled by the macros.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use feignhttp::{util, ClientConfig, TemplateValue, UriTemplate};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const URL: &str = "https://api.github.com/repos/{owner}/{repo}/issues{?page,per_page}";
+
+static URL_TEMPLATE: util::Lazy<UriTemplate> = util::Lazy::new(|| UriTemplate::parse(URL));
+
+fn maps() -> (HashMap<&'static str, TemplateValue>, HashMap<&'static str, String>) {
+    let mut path_map = HashMap::new();
+    path_map.insert("owner", TemplateValue::Str("dxx".to_string()));
+    path_map.insert("repo", TemplateValue::Str("feignhttp".to_string()));
+    let mut param_map = HashMap::new();
+    param_map.insert("page", "2".to_string());
+    param_map.insert("per_page", "10".to_string());
+    (path_map, param_map)
+}
+
+fn bench_url(c: &mut Criterion) {
+    let (path_map, param_map) = maps();
+    let mut group = c.benchmark_group("url");
+    group.bench_function("parse_per_call", |b| {
+        b.iter(|| {
+            let url = util::resolve_env(black_box(URL)).unwrap();
+            UriTemplate::parse(&url).expand(&path_map, &param_map).unwrap()
+        })
+    });
+    group.bench_function("precompiled", |b| {
+        b.iter(|| URL_TEMPLATE.expand(black_box(&path_map), &param_map).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_headers(c: &mut Criterion) {
+    let (_, param_map) = maps();
+    let mut group = c.benchmark_group("headers");
+    group.bench_function("replace_per_call", |b| {
+        b.iter(|| {
+            let key = util::replace(&util::resolve_env(black_box("accept")).unwrap(), &param_map);
+            let value = util::replace(
+                &util::resolve_env(black_box("application/json")).unwrap(),
+                &param_map,
+            );
+            (key, value)
+        })
+    });
+    group.bench_function("literal", |b| {
+        b.iter(|| (black_box("accept"), String::from(black_box("application/json"))))
+    });
+    group.finish();
+}
+
+fn bench_config(c: &mut Criterion) {
+    let mut group = c.benchmark_group("config");
+    group.bench_function("parse_per_call", |b| {
+        b.iter(|| {
+            let mut config = ClientConfig::default();
+            util::set_config(&mut config, "timeout", black_box("3000").to_string()).unwrap();
+            config
+        })
+    });
+    group.bench_function("precompiled", |b| {
+        b.iter(|| ClientConfig {
+            timeout: Some(Duration::from_millis(black_box(3000))),
+            ..Default::default()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_url, bench_headers, bench_config);
+criterion_main!(benches);
@@ -1,6 +1,7 @@
 use std::{fmt, str::FromStr};
 
 /// Http request method.
+#[allow(clippy::upper_case_acronyms)]
 pub enum Method {
     GET,
     POST,
@@ -30,6 +31,7 @@ impl Method {
 
 /// Arg type.
 #[derive(PartialEq, Clone)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum ArgType {
     HEADER,
     PATH,
@@ -37,6 +39,9 @@ pub enum ArgType {
     FORM,
     BODY,
     PARAM,
+    COOKIE,
+    DOWNLOAD,
+    PROGRESS,
 }
 
 impl fmt::Display for ArgType {
@@ -48,6 +53,9 @@ impl fmt::Display for ArgType {
             ArgType::FORM => "form",
             ArgType::BODY => "body",
             ArgType::PARAM => "PARAM",
+            ArgType::COOKIE => "cookie",
+            ArgType::DOWNLOAD => "download",
+            ArgType::PROGRESS => "progress",
         };
         write!(f, "{}", t)
     }
@@ -64,6 +72,9 @@ impl FromStr for ArgType {
             "form" => Ok(ArgType::FORM),
             "body" => Ok(ArgType::BODY),
             "param" => Ok(ArgType::PARAM),
+            "cookie" => Ok(ArgType::COOKIE),
+            "download" => Ok(ArgType::DOWNLOAD),
+            "progress" => Ok(ArgType::PROGRESS),
             _ => Err("unknown arg type: ".to_string() + s),
         }
     }
@@ -1,43 +1,68 @@
 use crate::enu::{ArgType, Method};
-use crate::util::{
-    parse_args_from_sig, parse_args_from_struct, parse_exprs, parse_return_type, parse_url_stream,
-    remove_url_attr,
-};
+use crate::meta::{AttrMeta, MetaValue, PROFILE_KEYS};
+use crate::util::{parse_args_from_sig, parse_args_from_struct, parse_return_type};
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
 use std::collections::HashMap;
 use std::str::FromStr;
 use syn::DataStruct;
 
-const CONFIG_KEYS: [&str; 2] = ["connect_timeout", "timeout"];
+const CONFIG_KEYS: [&str; 4] = ["connect_timeout", "timeout", "compress", "cache"];
+
+/// Content encodings supported by the `compress` metadata.
+const ENCODINGS: [&str; 3] = ["gzip", "deflate", "br"];
+
+/// Codecs supported by the `codec` metadata and their types.
+const CODECS: [(&str, &str); 4] = [
+    ("json", "JsonCodec"),
+    ("xml", "XmlCodec"),
+    ("msgpack", "MsgPackCodec"),
+    ("cbor", "CborCodec"),
+];
+
+/// Formats of `#[body(format)]`, binary formats are sent by their wrapper types.
+const BODY_FORMATS: [(&str, Option<&str>); 6] = [
+    ("json", None),
+    ("text", None),
+    ("bytes", None),
+    ("msgpack", Some("MsgPack")),
+    ("cbor", Some("Cbor")),
+    ("protobuf", Some("Protobuf")),
+];
 
 pub struct FnMetadata {
     // Url is a token stream, so it can be retrieved by a variable.
-    pub url: proc_macro2::TokenStream,
+    // It can be omitted when the `profile` metadata is specified.
+    pub url: Option<proc_macro2::TokenStream>,
+    // Paths are appended to the url in order.
+    pub paths: Vec<proc_macro2::TokenStream>,
     pub method: Method,
-    pub meta_map: HashMap<String, String>,
+    pub meta_map: HashMap<String, MetaValue>,
 }
 
 pub struct FnArg {
     pub arg_type: ArgType,
     pub name: String,
+    // A raw path value is not percent-encoded, like `#[path(raw)]`.
+    pub raw: bool,
+    // The format of a body, like `#[body(json)]`.
+    pub format: Option<syn::Ident>,
     pub var: syn::Ident,
     pub var_type: syn::Type,
 }
 
 pub fn http_impl(method: Method, attr: TokenStream, item: TokenStream) -> TokenStream {
-    let url = match parse_url_stream(&attr) {
-        Ok(url) => url,
+    let meta = match AttrMeta::parse_with_url(attr) {
+        Ok(meta) => meta,
         Err(err) => return err.into_compile_error().into(),
     };
 
-    let meta_map = parse_exprs(&remove_url_attr(&attr.to_string()));
-
     let stream = fn_impl(
         FnMetadata {
-            url,
+            url: meta.url_stream(),
+            paths: meta.path_stream().into_iter().collect(),
             method,
-            meta_map,
+            meta_map: meta.metas,
         },
         item,
         true,
@@ -49,11 +74,18 @@ pub fn http_impl(method: Method, attr: TokenStream, item: TokenStream) -> TokenS
 }
 
 pub fn client_fn_impl(mut item_struct: DataStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let cookie_jar = find_cookie_jar_field(&item_struct)?;
     let args = parse_args_from_struct(&mut item_struct)?;
 
     let (header_names, header_vars) = find_type_name_vars(&args, ArgType::HEADER, |_fn_arg| true);
 
-    let (path_names, path_vars) = find_type_name_vars(&args, ArgType::PATH, |_fn_arg| true);
+    let (path_names, path_vars) =
+        find_type_name_vars(&args, ArgType::PATH, |fn_arg| !fn_arg.raw && filter_query_array(fn_arg));
+
+    let (raw_path_names, raw_path_vars) = find_type_name_vars(&args, ArgType::PATH, |fn_arg| fn_arg.raw);
+
+    let (list_path_names, list_path_vars) =
+        find_type_name_vars(&args, ArgType::PATH, |fn_arg| !fn_arg.raw && !filter_query_array(fn_arg));
 
     let (query_names, query_vars) = find_type_name_vars(&args, ArgType::QUERY, filter_query_array);
 
@@ -62,6 +94,13 @@ pub fn client_fn_impl(mut item_struct: DataStruct) -> syn::Result<proc_macro2::T
 
     let (param_names, param_vars) = find_type_name_vars(&args, ArgType::PARAM, |_fn_arg| true);
 
+    let (cookie_names, cookie_vars) = find_type_name_vars(&args, ArgType::COOKIE, |_fn_arg| true);
+
+    let cookie_jar = match cookie_jar {
+        Some(field) => quote!(::std::clone::Clone::clone(&self.#field).into()),
+        None => quote!(None),
+    };
+
     let tokens = quote!(
         fn param_map(&self) -> ::std::collections::HashMap<&str, String> {
             let mut out = ::std::collections::HashMap::new();
@@ -71,7 +110,7 @@ pub fn client_fn_impl(mut item_struct: DataStruct) -> syn::Result<proc_macro2::T
             out
         }
 
-        fn header_map(&self) -> ::std::collections::HashMap<std::borrow::Cow<str>, String> {
+        fn header_map(&self) -> ::std::collections::HashMap<std::borrow::Cow<'_, str>, String> {
             let mut out = ::std::collections::HashMap::new();
             #(
                 out.insert(std::borrow::Cow::Borrowed(#header_names), format!("{}", self.#header_vars));
@@ -79,10 +118,18 @@ pub fn client_fn_impl(mut item_struct: DataStruct) -> syn::Result<proc_macro2::T
             out
         }
 
-        fn path_map(&self) -> ::std::collections::HashMap<&str, String> {
+        fn path_map(&self) -> ::std::collections::HashMap<&str, ::feignhttp::TemplateValue> {
             let mut out = ::std::collections::HashMap::new();
             #(
-                out.insert(#path_names, format!("{}", self.#path_vars));
+                out.insert(#path_names, ::feignhttp::TemplateValue::Str(format!("{}", self.#path_vars)));
+            )*
+            #(
+                out.insert(#raw_path_names, ::feignhttp::TemplateValue::Raw(format!("{}", self.#raw_path_vars)));
+            )*
+            #(
+                out.insert(#list_path_names, ::feignhttp::TemplateValue::List(
+                    self.#list_path_vars.iter().map(|v| format!("{}", v)).collect(),
+                ));
             )*
             out
         }
@@ -101,37 +148,113 @@ pub fn client_fn_impl(mut item_struct: DataStruct) -> syn::Result<proc_macro2::T
             )*
             query_vec
         }
+
+        fn cookie_map(&self) -> Vec<(&str, String)> {
+            let mut cookie_vec: Vec<(&str, String)> = Vec::new();
+            #(
+                cookie_vec.push((#cookie_names, format!("{}", self.#cookie_vars)));
+            )*
+            cookie_vec
+        }
+
+        fn cookie_jar(&self) -> Option<::feignhttp::CookieJar> {
+            #cookie_jar
+        }
     );
 
     Ok(tokens)
 }
 
+/// Find the field marked with `#[cookie_jar]`.
+fn find_cookie_jar_field(item_struct: &DataStruct) -> syn::Result<Option<syn::Member>> {
+    let mut found = None;
+    for (i, field) in item_struct.fields.iter().enumerate() {
+        if !field.attrs.iter().any(|attr| attr.path.is_ident("cookie_jar")) {
+            continue;
+        }
+        if found.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "struct must have only one cookie_jar",
+            ));
+        }
+        found = Some(match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(i.into()),
+        });
+    }
+    Ok(found)
+}
+
 /// Generate function code.
 pub fn fn_impl(
     metadata: FnMetadata,
     item_stream: TokenStream,
     empty_maps: bool,
 ) -> syn::Result<proc_macro2::TokenStream> {
-    let url = metadata.url;
     let method = metadata.method.to_str();
     let meta_map = metadata.meta_map;
+    let paths = metadata.paths;
+    let url_template = metadata.url.clone();
+    let url = match metadata.url {
+        Some(url) => quote!(Some((#url).to_string())),
+        None => quote!(None),
+    };
+    let profile = PROFILE_KEYS.iter().find_map(|k| meta_map.get(*k));
+    let client_config = match profile {
+        Some(name) => quote!(feignhttp::ClientConfig::named(&util::resolve_env(#name)?)?),
+        None => quote!(feignhttp::ClientConfig::default()),
+    };
 
+    // Literal config values are validated and converted here, others are parsed at call time.
     let mut config_keys = Vec::new();
     let mut config_values = Vec::new();
-    for (k, v) in meta_map.iter() {
-        if !CONFIG_KEYS.contains(&k.as_str()) {
-            continue;
+    let mut const_configs = Vec::new();
+    for key in CONFIG_KEYS {
+        let value = match meta_map.get(key) {
+            Some(value) => value,
+            None => continue,
+        };
+        if is_dynamic(&value.value) {
+            config_keys.push(key);
+            config_values.push(value);
+        } else {
+            const_configs.push(const_config(key, value)?);
         }
-        config_keys.push(k);
-        config_values.push(v);
     }
 
     let (header_keys, header_values) = match meta_map.get("headers") {
-        Some(val) => parse_header_values(&val)?,
+        Some(val) => parse_header_values(val)?,
         None => (vec![], vec![]),
     };
+    let (mut const_header_keys, mut const_header_values) = (vec![], vec![]);
+    let (mut dynamic_header_keys, mut dynamic_header_values) = (vec![], vec![]);
+    for (key, value) in header_keys.into_iter().zip(header_values) {
+        if is_dynamic(&key) || is_dynamic(&value) {
+            dynamic_header_keys.push(key);
+            dynamic_header_values.push(value);
+        } else {
+            const_header_keys.push(key);
+            const_header_values.push(value);
+        }
+    }
+
+    let (query_keys, query_values) = match meta_map.get("query") {
+        Some(val) => parse_query_values(val)?,
+        None => (vec![], vec![]),
+    };
+    // Query pairs keep the declared order, values with placeholders are resolved at call time.
+    let query_pushes: Vec<proc_macro2::TokenStream> = query_keys
+        .iter()
+        .zip(&query_values)
+        .map(|(key, value)| match is_dynamic(value) {
+            true => quote!(query_vec.push((#key, util::replace(&util::resolve_env(#value)?, &param_map)));),
+            false => quote!(query_vec.push((#key, String::from(#value)));),
+        })
+        .collect();
 
     let mut item_fn = syn::parse::<syn::ItemFn>(item_stream)?;
+    let paginate = parse_paginate(&mut item_fn.attrs)?;
 
     let sig = &mut item_fn.sig;
     let asyncness = &sig.asyncness;
@@ -152,6 +275,17 @@ pub fn fn_impl(
 
     let (path_names, path_vars) = find_type_name_vars(&args, ArgType::PATH, |_fn_arg| true);
 
+    let (param_names, param_vars) = find_type_name_vars(&args, ArgType::PARAM, |_fn_arg| true);
+
+    // Valid url placeholders.
+    validate_placeholders(url_template.as_ref(), &paths, &path_names, &path_vars, &param_names, sig, empty_maps)?;
+
+    let (raw_path_names, raw_path_vars) = find_type_name_vars(&args, ArgType::PATH, |fn_arg| fn_arg.raw);
+    let (list_path_names, list_path_vars) =
+        find_type_name_vars(&args, ArgType::PATH, |fn_arg| !fn_arg.raw && !filter_query_array(fn_arg));
+    let (path_names, path_vars) =
+        find_type_name_vars(&args, ArgType::PATH, |fn_arg| !fn_arg.raw && filter_query_array(fn_arg));
+
     let (query_names, query_vars) =
         find_type_name_vars(&args, ArgType::QUERY, |fn_arg| filter_query_array(fn_arg) && filter_struct(fn_arg));
 
@@ -163,12 +297,37 @@ pub fn fn_impl(
 
     let (form_names, form_vars) = find_type_name_vars(&args, ArgType::FORM, |_fn_arg| true);
 
-    let (param_names, param_vars) = find_type_name_vars(&args, ArgType::PARAM, |_fn_arg| true);
+    let (cookie_names, cookie_vars) = find_type_name_vars(&args, ArgType::COOKIE, |_fn_arg| true);
 
     let body_vars = find_type_vars(&args, ArgType::BODY, |_fn_arg| true);
 
+    let download_vars = find_type_vars(&args, ArgType::DOWNLOAD, |_fn_arg| true);
+
+    let progress_vars = find_type_vars(&args, ArgType::PROGRESS, |_fn_arg| true);
+    if progress_vars.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            &sig.inputs,
+            "request must have only one progress",
+        ));
+    }
+    add_progress_bounds(sig, &progress_vars);
+
+    let codec = get_codec(meta_map.get("codec"))?;
+    let csv_config = get_csv_config(&meta_map)?;
+
+    let body_template = meta_map.get("body");
+    // `content_type` is an alias of `consumes` which takes precedence.
+    let content_type = meta_map.get("content_type").or_else(|| meta_map.get("consumes"));
+    let content_types: Vec<&MetaValue> = content_type.into_iter().collect();
+    let produces: Vec<&MetaValue> = meta_map.get("produces").into_iter().collect();
+
     // Valid form and body.
-    if form_vars.len() > 0 && body_vars.len() > 0 {
+    if body_template.is_some() && (!form_vars.is_empty() || !body_vars.is_empty()) {
+        return Err(syn::Error::new_spanned(
+            &sig.inputs,
+            "request must have only one of body metadata, body or form",
+        ));
+    } else if !form_vars.is_empty() && !body_vars.is_empty() {
         return Err(syn::Error::new_spanned(
             &sig.inputs,
             "request must have only one of body or form",
@@ -181,7 +340,7 @@ pub fn fn_impl(
     }
 
     // Valid param types.
-    if param_names.len() > 0 {
+    if !param_names.is_empty() {
         let param_types = find_arg_types(&args, ArgType::PARAM);
         for i in 0..param_types.len() {
             let p_name = param_names.get(i).unwrap();
@@ -200,10 +359,47 @@ pub fn fn_impl(
         }
     }
 
+    // Every page is requested again, so a paginated request has no body.
+    if let Some(paginate) = &paginate {
+        if body_template.is_some() || !body_vars.is_empty() || !form_vars.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &paginate.attr,
+                "paginate does not support a request body",
+            ));
+        }
+    }
+
+    // A download may be resumed by sending the request again, so it has no body.
+    if download_vars.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            &sig.inputs,
+            "request must have only one download",
+        ));
+    } else if !download_vars.is_empty() {
+        if body_template.is_some() || !body_vars.is_empty() || !form_vars.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "download does not support a request body",
+            ));
+        }
+        if paginate.is_some() {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "download can't be used with paginate",
+            ));
+        }
+    }
+
     let mut send_fn_call = quote! {send()};
-    if !body_vars.is_empty() {
-        let body_types = find_arg_types(&args, ArgType::BODY);
-        send_fn_call = get_body_fn_call(body_types.get(0).unwrap(), body_vars.get(0).unwrap());
+    if let Some(template) = body_template {
+        if empty_maps {
+            validate_body_placeholders(template, &param_names)?;
+        }
+        let content_type = content_type.map(|c| c.value.as_str()).unwrap_or("text/plain");
+        send_fn_call = quote! {send_text(util::render_body(#template, &param_map, #content_type)?)};
+    } else if !body_vars.is_empty() {
+        let body_arg = args.iter().find(|arg| arg.arg_type == ArgType::BODY).unwrap();
+        send_fn_call = get_body_fn_call(body_arg, &codec)?;
     } else if !form_vars.is_empty() {
         let form_types = find_arg_types(&args, ArgType::FORM);
         match get_form_fn_call(&form_names, &form_types, &form_vars) {
@@ -223,8 +419,49 @@ pub fn fn_impl(
             "function must have generic parameters",
         ));
     }
-    let return_type = return_args.get(0).unwrap();
-    let return_fn = get_return_fn(return_type);
+    let return_type = &return_args[0];
+    if !download_vars.is_empty() && !is_download_type(return_type) {
+        return Err(syn::Error::new_spanned(
+            &sig.output,
+            "download must return `feignhttp::Result<feignhttp::Download>`",
+        ));
+    }
+
+    // The response content type is checked against `produces`, or the media type of the return type.
+    let expected_content_type = match meta_map.get("produces") {
+        Some(produces) => quote! { Some(#produces) },
+        None => quote! { util::Decode::<#return_type, _>::new(#codec).accept() },
+    };
+
+    // A return type configured by csv metadata is received by `FromCsv`.
+    let decode_stream = match csv_config {
+        Some(csv_config) => quote! {
+            <#return_type as feignhttp::FromCsv>::from_csv(response, #csv_config).await?
+        },
+        None => quote! { util::Decode::<#return_type, _>::new(#codec).decode(response).await? },
+    };
+
+    // A literal url is parsed once, others are resolved and parsed at call time.
+    let url_stream = match literal_template(url_template.as_ref(), &paths) {
+        Some((base, literal_paths)) => quote! {{
+            static URL_TEMPLATE: util::Lazy<feignhttp::UriTemplate> = util::Lazy::new(|| {
+                let mut url = String::from(#base);
+                #(
+                    url = util::join_url(&url, #literal_paths);
+                )*
+                feignhttp::UriTemplate::parse(&url)
+            });
+            URL_TEMPLATE.expand(&path_map, &param_map)?
+        }},
+        None => quote! {{
+            let mut url = util::base_url(&client_config, #url)?;
+            #(
+                url = util::join_url(&url, &(#paths).to_string());
+            )*
+            // Environment placeholders are resolved before the url template is expanded.
+            feignhttp::UriTemplate::parse(&util::resolve_env(&url)?).expand(&path_map, &param_map)?
+        }},
+    };
 
     #[rustfmt::skip]
     let param_map = if empty_maps { quote! ( HashMap::new() ) } else { quote! ( self.param_map() ) };
@@ -234,77 +471,173 @@ pub fn fn_impl(
     let path_map = if empty_maps { quote! ( HashMap::new() ) } else { quote! ( self.path_map() ) };
     #[rustfmt::skip]
     let query_map = if empty_maps { quote! ( Vec::new() ) } else { quote! ( self.query_map() ) };
+    #[rustfmt::skip]
+    let cookie_map = if empty_maps { quote! ( Vec::new() ) } else { quote! ( self.cookie_map() ) };
+    #[rustfmt::skip]
+    let cookie_jar = if empty_maps { quote! ( None::<feignhttp::CookieJar> ) } else { quote! ( self.cookie_jar() ) };
+
+    let uses = quote! {
+        use feignhttp::FeignClient as _;
+        use std::collections::HashMap;
+        use feignhttp::{HttpClient, HttpConfig, HttpResponse, ser, util};
+        use feignhttp::util::{DecodeCodec as _, DecodeResponse as _, EncodeBody as _, EncodeCodec as _};
+        use std::borrow::Cow;
+    };
 
-    let stream = quote! {
-        #vis #sig {
-            use feignhttp::FeignClient as _;
-            use std::collections::HashMap;
-            use feignhttp::{HttpClient, HttpConfig, HttpResponse, ser, util};
-            use std::borrow::Cow;
+    // The media type of the return type is accepted if no `accept` header is set, a download accepts any.
+    let default_accept = match download_vars.is_empty() {
+        true => quote! {
+            if let Some(accept) = util::Decode::<#return_type, _>::new(#codec).accept() {
+                if !header_map.keys().any(|key| key.eq_ignore_ascii_case("accept")) {
+                    header_map.insert(Cow::Borrowed("accept"), accept.to_string());
+                }
+            }
+        },
+        false => quote! {},
+    };
 
-            let mut param_map: HashMap<&str, String> = #param_map;
-            #(
-                param_map.insert(#param_names, format!("{}", #param_vars));
-            )*
+    // Build the request, it is sent once or for every page of a paginated function.
+    let build_request = quote! {
+        let mut param_map: HashMap<&str, String> = #param_map;
+        #(
+            param_map.insert(#param_names, format!("{}", #param_vars));
+        )*
+
+        // The profile supplies defaults, the metadata in the attribute overrides them.
+        let mut client_config = #client_config;
+        #(
+            #const_configs
+        )*
+        #(
+            let value = util::replace(&util::resolve_env(#config_values)?, &param_map);
+            util::set_config(&mut client_config, #config_keys, value)?;
+        )*
+
+        let mut header_map: HashMap<Cow<str>, String> = HashMap::new();
+        // Headers in the profile have the lowest priority.
+        for (key, value) in &client_config.headers {
+            header_map.insert(Cow::Owned(key.clone()), util::resolve_env(value)?);
+        }
+        header_map.extend(#header_map);
+
+        // Header in `#[get("", headers="")]` added before header in `#[header]` added.
+        #(
+            header_map.insert(Cow::Borrowed(#const_header_keys), String::from(#const_header_values));
+        )*
+        #(
+            let key = util::replace(&util::resolve_env(#dynamic_header_keys)?, &param_map);
+            let value = util::replace(&util::resolve_env(#dynamic_header_values)?, &param_map);
+            header_map.insert(Cow::Owned(key), value);
+        )*
+
+        // Content type in metadata added before header in `#[header]` added.
+        #(
+            header_map.insert(Cow::Borrowed("content-type"), String::from(#content_types));
+        )*
+        #(
+            header_map.insert(Cow::Borrowed("accept"), String::from(#produces));
+        )*
+
+        #(
+            header_map.insert(Cow::Borrowed(#header_names), #header_vars.to_string());
+        )*
+
+        #(
+            let map = ser::to_map(& #header_struct_vars)?;
+            for (key, value) in map {
+                header_map.insert(Cow::Owned(key), value);
+            }
+        )*
+
+        #default_accept
+
+        // Path values are percent-encoded when the url template is expanded unless they are raw.
+        let mut path_map: HashMap<&str, feignhttp::TemplateValue> = #path_map;
+        #(
+            path_map.insert(#path_names, feignhttp::TemplateValue::Str(#path_vars.to_string()));
+        )*
+        #(
+            path_map.insert(#raw_path_names, feignhttp::TemplateValue::Raw(#raw_path_vars.to_string()));
+        )*
+        #(
+            path_map.insert(#list_path_names, feignhttp::TemplateValue::List(
+                #list_path_vars.iter().map(|v| v.to_string()).collect(),
+            ));
+        )*
+
+        // Query in `#[get("", query="")]` added before query of the struct and `#[query]` added.
+        let mut query_vec: Vec<(&str, String)> = Vec::new();
+        #(
+            #query_pushes
+        )*
+        query_vec.extend(#query_map);
+        #(
+            query_vec.push((#query_names, #query_vars.to_string()));
+        )*
+
+        #(
+            let query_array_name = #query_array_names;
+            for query_array_var in #query_array_vars {
+                query_vec.push((query_array_name, query_array_var.to_string()));
+            }
+        )*
 
-            let mut config_map: HashMap<&str, String> = HashMap::new();
-            #(
-                config_map.insert(#config_keys, util::replace(#config_values, &param_map));
-            )*
+        #(
+            let map = ser::to_map(& #query_struct_vars)?;
+            for (key, value) in map.iter() {
+                query_vec.push((key.as_str(), value.to_string()));
+            }
+        )*
 
-            let mut header_map: HashMap<Cow<str>, String> = #header_map;
+        let mut cookie_vec: Vec<(&str, String)> = #cookie_map;
+        #(
+            cookie_vec.push((#cookie_names, #cookie_vars.to_string()));
+        )*
 
-            // Header in `#[get("", headers="")]` added before header in `#[header]` added.
-            #(
-                let key = util::replace(#header_keys, &param_map);
-                let value = util::replace(#header_values, &param_map);
-                header_map.insert(Cow::Owned(key), value);
-            )*
+        let url = #url_stream;
 
-            #(
-                header_map.insert(Cow::Borrowed(#header_names), #header_vars.to_string());
-            )*
+        let config = HttpConfig::default().with_client_config(&client_config)?;
 
-            #(
-                let map = ser::to_map(& #header_struct_vars)?;
-                for (key, value) in map {
-                    header_map.insert(Cow::Owned(key), value);
-                }
-            )*
+        let mut builder = HttpClient::builder().url(&url).method(#method).config(config)
+            .headers(header_map).query(query_vec).cookies(cookie_vec);
+        if let Some(cookie_jar) = #cookie_jar {
+            builder = builder.cookie_jar(cookie_jar);
+        }
+        #(
+            builder = builder.progress(#progress_vars);
+        )*
+        let request = builder.build()?;
+    };
 
-            let mut path_map: HashMap<&str, String> = #path_map;
-            #(
-                path_map.insert(#path_names, #path_vars.to_string());
-            )*
+    if let Some(paginate) = paginate {
+        return paginate_impl(paginate, item_fn, return_type, uses, build_request, expected_content_type, codec);
+    }
 
-            let mut query_vec: Vec<(&str, String)> = #query_map;
-            #(
-                query_vec.push((#query_names, #query_vars.to_string()));
-            )*
+    if let Some(download_var) = download_vars.first() {
+        return Ok(quote! {
+            #vis #sig {
+                #uses
 
-            #(
-                let query_array_name = #query_array_names;
-                for query_array_var in #query_array_vars {
-                    query_vec.push((query_array_name, query_array_var.to_string()));
-                }
-            )*
+                #build_request
 
-            #(
-                let map = ser::to_map(& #query_struct_vars)?;
-                for (key, value) in map.iter() {
-                    query_vec.push((key.as_str(), value.to_string()));
-                }
-            )*
+                let return_value: #return_type = feignhttp::Download::start(request, #download_var).await?;
 
-            let url = util::replace(&format!("{}", #url), &path_map);
+                Ok(return_value)
+            }
+        });
+    }
 
-            let config = HttpConfig::from_map(config_map)?;
+    let stream = quote! {
+        #vis #sig {
+            #uses
 
-            let request = HttpClient::builder().url(&url).method(#method).config(config)
-                .headers(header_map).query(query_vec).build()?;
+            #build_request
 
             let response = request.#send_fn_call.await?;
-            let return_value: #return_type = response.#return_fn().await?;
+            if let Some(expected) = #expected_content_type {
+                util::check_content_type(response.headers(), expected)?;
+            }
+            let return_value: #return_type = #decode_stream;
 
             Ok(return_value)
         }
@@ -314,7 +647,7 @@ pub fn fn_impl(
 }
 
 fn find_type_name_vars(
-    args: &Vec<FnArg>,
+    args: &[FnArg],
     arg_type: ArgType,
     filter: impl Fn(&FnArg) -> bool,
 ) -> (Vec<String>, Vec<syn::Ident>) {
@@ -331,7 +664,7 @@ fn find_type_name_vars(
 }
 
 fn find_type_vars(
-    args: &Vec<FnArg>,
+    args: &[FnArg],
     arg_type: ArgType,
     filter: impl Fn(&FnArg) -> bool,
 ) -> Vec<syn::Ident> {
@@ -342,7 +675,7 @@ fn find_type_vars(
         .collect()
 }
 
-fn find_arg_types(args: &Vec<FnArg>, arg_type: ArgType) -> Vec<syn::Type> {
+fn find_arg_types(args: &[FnArg], arg_type: ArgType) -> Vec<syn::Type> {
     args.iter()
         .filter(|arg| arg.arg_type == arg_type)
         .map(|arg| arg.var_type.clone())
@@ -369,55 +702,467 @@ fn filter_struct(arg: &FnArg) -> bool {
     }
 }
 
-fn parse_header_values(s: &str) -> syn::Result<(Vec<String>, Vec<String>)> {
-    let (mut key_vec, mut value_vec) = (vec![], vec![]);
-    if s.len() <= 0 {
-        return Ok((key_vec, value_vec));
+/// Check the placeholders of literal url and paths against path and param arguments.
+/// A method of a struct can get values from `#[url_path]` and `#[param]` fields, so only unused arguments are checked.
+fn validate_placeholders(
+    url: Option<&proc_macro2::TokenStream>,
+    paths: &[proc_macro2::TokenStream],
+    path_names: &[String],
+    path_vars: &[syn::Ident],
+    param_names: &[String],
+    sig: &syn::Signature,
+    is_fn: bool,
+) -> syn::Result<()> {
+    let templates: Vec<Option<syn::LitStr>> = url
+        .into_iter()
+        .chain(paths)
+        .map(|t| syn::parse2::<syn::LitStr>(t.clone()).ok())
+        .collect();
+    let mut placeholders = Vec::new();
+    for lit in templates.iter().flatten() {
+        for name in template_placeholders(&lit.value()) {
+            if is_fn && !path_names.contains(&name) && !param_names.contains(&name) {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!("placeholder {{{}}} has no matching #[path] or #[param] argument", name),
+                ));
+            }
+            placeholders.push(name);
+        }
+    }
+
+    // Placeholders of a url expression or the base url of a profile are unknown.
+    if url.is_none() || templates.iter().any(Option::is_none) {
+        return Ok(());
     }
-    let s_split = s.split(";");
-    for header_str in s_split {
-        let header_split = header_str.split(":");
-        let header_vec: Vec<&str> = header_split.into_iter().collect();
-        if header_vec.len() != 2 {
+    for (name, var) in path_names.iter().zip(path_vars) {
+        if placeholders.contains(name) {
+            continue;
+        }
+        let arg = sig.inputs.iter().find(|arg| match arg {
+            syn::FnArg::Typed(pat_type) => match &*pat_type.pat {
+                syn::Pat::Ident(pat) => pat.ident == *var,
+                _ => false,
+            },
+            _ => false,
+        });
+        let msg = format!("#[path] argument {} has no matching placeholder in url", name);
+        return Err(match arg {
+            Some(arg) => syn::Error::new_spanned(arg, msg),
+            None => syn::Error::new_spanned(&sig.inputs, msg),
+        });
+    }
+    Ok(())
+}
+
+/// Check the placeholders of the body template against param arguments.
+fn validate_body_placeholders(template: &MetaValue, param_names: &[String]) -> syn::Result<()> {
+    for name in body_placeholders(&template.value) {
+        if !param_names.iter().any(|p| p == name) {
             return Err(syn::Error::new(
-                proc_macro2::Span::call_site(),
-                format!("headers format is incorrect: {}", header_str),
+                template.span,
+                format!("placeholder {{{}}} in body has no matching #[param] argument", name),
             ));
         }
-        let k = header_vec[0].trim().to_string();
-        if k.len() == 0 {
+    }
+    Ok(())
+}
+
+/// Get the names of `{name}` placeholders in a body template, other braces like JSON objects are skipped.
+fn body_placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        if end > 0 && rest[end..].starts_with('}') {
+            names.push(&rest[..end]);
+        }
+    }
+    names
+}
+
+/// Get the variable names of URI template expressions like `{name}`, `{+path}` or `{?page,per_page}`,
+/// environment placeholders like `${NAME}` are skipped.
+fn template_placeholders(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let is_env = rest[..start].ends_with('$');
+        rest = &rest[start + 1..];
+        let end = match rest.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+        let expression = &rest[..end];
+        let list = expression.strip_prefix(['+', '#', '.', '/', ';', '?', '&']).unwrap_or(expression);
+        let vars: Vec<&str> = list
+            .split(',')
+            .map(|spec| match spec.split_once(':') {
+                Some((name, _)) => name,
+                None => spec.strip_suffix('*').unwrap_or(spec),
+            })
+            .collect();
+        let valid = vars.iter().all(|name| {
+            !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+        });
+        if !is_env && valid {
+            names.extend(vars.into_iter().map(String::from));
+        }
+        rest = &rest[end + 1..];
+    }
+    names
+}
+
+/// Get the url and paths if they are literals without environment placeholders.
+fn literal_template(
+    url: Option<&proc_macro2::TokenStream>,
+    paths: &[proc_macro2::TokenStream],
+) -> Option<(String, Vec<String>)> {
+    let mut parts = Vec::new();
+    for part in std::iter::once(url?).chain(paths) {
+        let part = syn::parse2::<syn::LitStr>(part.clone()).ok()?.value();
+        if part.contains("${") {
+            return None;
+        }
+        parts.push(part);
+    }
+    let base = parts.remove(0);
+    Some((base, parts))
+}
+
+/// A value with placeholders like `{param}` or `${ENV}` is resolved at call time.
+fn is_dynamic(value: &str) -> bool {
+    value.contains('{')
+}
+
+/// The metadata of `#[paginate(...)]`.
+struct Paginate {
+    attr: syn::Attribute,
+    pagination: proc_macro2::TokenStream,
+    // The items and cursor fields of the response body in cursor pagination.
+    cursor_fields: Option<(String, String)>,
+    max_pages: Option<syn::LitInt>,
+}
+
+/// Parse and remove the `#[paginate]` attribute of a function.
+fn parse_paginate(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Option<Paginate>> {
+    let attr = match attrs.iter().position(|attr| attr.path.is_ident("paginate")) {
+        Some(i) => attrs.remove(i),
+        None => return Ok(None),
+    };
+    let metas = match attr.parse_meta()? {
+        syn::Meta::List(list) => list.nested,
+        _ => return Err(syn::Error::new_spanned(&attr, "paginate must have one of link_header, cursor or page")),
+    };
+    let (mut link_header, mut cursor, mut param, mut items, mut page, mut max_pages) =
+        (false, None, None, None, None, None);
+    for meta in metas.iter() {
+        match meta {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("link_header") => link_header = true,
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) => {
+                let key = name_value.path.get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+                match (key.as_str(), &name_value.lit) {
+                    ("cursor", syn::Lit::Str(lit)) => cursor = Some(lit.value()),
+                    ("param", syn::Lit::Str(lit)) => param = Some(lit.value()),
+                    ("items", syn::Lit::Str(lit)) => items = Some(lit.value()),
+                    ("page", syn::Lit::Str(lit)) => page = Some(lit.value()),
+                    ("max_pages", syn::Lit::Int(lit)) => max_pages = Some(lit.clone()),
+                    _ => return Err(syn::Error::new_spanned(meta, "unknown paginate metadata")),
+                }
+            }
+            _ => return Err(syn::Error::new_spanned(meta, "unknown paginate metadata")),
+        }
+    }
+    if cursor.is_none() && (param.is_some() || items.is_some()) {
+        return Err(syn::Error::new_spanned(&attr, "param and items are only used with cursor"));
+    }
+    let (pagination, cursor_fields) = match (link_header, cursor, page) {
+        (true, None, None) => (quote!(feignhttp::Pagination::LinkHeader), None),
+        (false, Some(cursor), None) => {
+            let param = param.unwrap_or_else(|| "cursor".to_string());
+            let items = items.unwrap_or_else(|| "items".to_string());
+            (quote!(feignhttp::Pagination::Cursor { param: #param }), Some((items, cursor)))
+        }
+        (false, None, Some(page)) => (quote!(feignhttp::Pagination::Page { param: #page }), None),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &attr,
+                "paginate must have one of link_header, cursor or page",
+            ))
+        }
+    };
+    Ok(Some(Paginate {
+        attr,
+        pagination,
+        cursor_fields,
+        max_pages,
+    }))
+}
+
+/// Generate a function which returns a stream of the items of all pages.
+fn paginate_impl(
+    paginate: Paginate,
+    mut item_fn: syn::ItemFn,
+    return_type: &syn::Type,
+    uses: proc_macro2::TokenStream,
+    build_request: proc_macro2::TokenStream,
+    expected_content_type: proc_macro2::TokenStream,
+    codec: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let item_type = vec_item_type(return_type).ok_or_else(|| {
+        syn::Error::new_spanned(return_type, "paginate must return a list like `feignhttp::Result<Vec<T>>`")
+    })?;
+    item_fn.sig.asyncness = None;
+    item_fn.sig.output = syn::parse_quote!(-> feignhttp::Pages<#item_type>);
+    let vis = &item_fn.vis;
+    let sig = &item_fn.sig;
+
+    // The items and the cursor are fields of the response body, the body is the list otherwise.
+    let (page_struct, decode_page) = match &paginate.cursor_fields {
+        Some((items, cursor)) => (
+            quote! {
+                #[derive(feignhttp::util::serde::Deserialize)]
+                #[serde(crate = "feignhttp::util::serde")]
+                struct CursorPage {
+                    #[serde(rename = #items)]
+                    items: Vec<#item_type>,
+                    #[serde(rename = #cursor, default)]
+                    cursor: Option<util::Cursor>,
+                }
+            },
+            quote! {
+                let page: CursorPage = util::Decode::<CursorPage, _>::new(#codec).decode(response).await?;
+                feignhttp::Result::Ok(util::Page { items: page.items, cursor: page.cursor.map(|cursor| cursor.0) })
+            },
+        ),
+        None => (
+            quote!(),
+            quote! {
+                let items: #return_type = util::Decode::<#return_type, _>::new(#codec).decode(response).await?;
+                feignhttp::Result::Ok(util::Page { items, cursor: None })
+            },
+        ),
+    };
+    let pagination = &paginate.pagination;
+    let max_pages = paginate.max_pages.iter();
+
+    Ok(quote! {
+        #vis #sig {
+            #uses
+            #page_struct
+
+            let request = (|| -> feignhttp::Result<_> {
+                #build_request
+                Ok(request)
+            })();
+            feignhttp::Pages::new(request, #pagination, |response| Box::pin(async move {
+                if let Some(expected) = #expected_content_type {
+                    util::check_content_type(response.headers(), expected)?;
+                }
+                #decode_page
+            }))
+            #(.max_pages(#max_pages))*
+        }
+    })
+}
+
+/// The progress callback is kept by the request, so `impl Fn(Progress)` is made `Send + Sync + 'static`.
+fn add_progress_bounds(sig: &mut syn::Signature, progress_vars: &[syn::Ident]) {
+    for input in sig.inputs.iter_mut() {
+        let pat_type = match input {
+            syn::FnArg::Typed(pat_type) => pat_type,
+            _ => continue,
+        };
+        let is_progress = match &*pat_type.pat {
+            syn::Pat::Ident(pat) => progress_vars.contains(&pat.ident),
+            _ => false,
+        };
+        if let (true, syn::Type::ImplTrait(impl_trait)) = (is_progress, &mut *pat_type.ty) {
+            for bound in ["Send", "Sync", "'static"] {
+                if !impl_trait.bounds.iter().any(|b| b.to_token_stream().to_string() == bound) {
+                    impl_trait.bounds.push(syn::parse_str(bound).unwrap());
+                }
+            }
+        }
+    }
+}
+
+/// Whether the type is `Download`.
+fn is_download_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path.path.segments.last().is_some_and(|segment| segment.ident == "Download"),
+        _ => false,
+    }
+}
+
+/// The item type of `Vec<T>`.
+fn vec_item_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if segment.ident == "Vec" && args.args.len() == 1 => {
+            match args.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Move `#[paginate]` after the method attribute of the function, which handles it.
+pub fn paginate_attr_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut item_fn = syn::parse_macro_input!(item as syn::ItemFn);
+    let method_attr = item_fn.attrs.iter().position(|attr| {
+        let name = attr.path.segments.last().unwrap().ident.to_string();
+        Method::from_str(&name).is_ok()
+    });
+    match method_attr {
+        Some(i) => {
+            let attr = proc_macro2::TokenStream::from(attr);
+            item_fn.attrs.insert(i + 1, syn::parse_quote!(#[paginate(#attr)]));
+            item_fn.into_token_stream().into()
+        }
+        None => syn::Error::new_spanned(&item_fn.sig, "paginate must be used with a method attribute like #[get]")
+            .into_compile_error()
+            .into(),
+    }
+}
+
+/// Convert a literal config value to a statement that sets the client config.
+fn const_config(key: &str, value: &MetaValue) -> syn::Result<proc_macro2::TokenStream> {
+    let stream = match key {
+        "compress" => {
+            let encoding = value.value.trim().to_lowercase();
+            if !ENCODINGS.contains(&encoding.as_str()) {
+                return Err(syn::Error::new(
+                    value.span,
+                    format!(
+                        "unsupported compress encoding: {}, expected one of {}",
+                        encoding,
+                        ENCODINGS.join(", ")
+                    ),
+                ));
+            }
+            quote!(client_config.compress = Some(String::from(#encoding));)
+        }
+        "cache" => {
+            let cache = value.value.trim();
+            let is_disk = cache.strip_prefix("disk:").is_some_and(|dir| !dir.trim().is_empty());
+            if cache != "memory" && cache != "disk" && !is_disk {
+                return Err(syn::Error::new(
+                    value.span,
+                    format!("unsupported cache: {}, expected memory, disk or disk:<dir>", cache),
+                ));
+            }
+            quote!(client_config.cache = Some(String::from(#cache));)
+        }
+        _ => {
+            let millis = parse_duration_millis(&value.value).ok_or_else(|| {
+                syn::Error::new(value.span, format!("invalid duration: {}", value.value))
+            })?;
+            let field = quote::format_ident!("{}", key);
+            quote!(client_config.#field = Some(std::time::Duration::from_millis(#millis));)
+        }
+    };
+    Ok(stream)
+}
+
+/// Parse a duration like `3000`, `5s` or `1m30s` to milliseconds, the same as `feignhttp::parse_duration`.
+fn parse_duration_millis(s: &str) -> Option<u64> {
+    let s = s.trim();
+    if let Ok(millis) = s.parse::<u64>() {
+        return Some(millis);
+    }
+    let mut millis = 0u64;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        if digits == 0 {
+            return None;
+        }
+        let value = rest[..digits].parse::<u64>().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let unit = match &rest[..unit_len] {
+            "ms" => 1,
+            "s" => 1000,
+            "m" => 60 * 1000,
+            "h" => 60 * 60 * 1000,
+            _ => return None,
+        };
+        millis = millis.checked_add(value.checked_mul(unit)?)?;
+        rest = &rest[unit_len..];
+    }
+    Some(millis)
+}
+
+fn parse_query_values(query: &MetaValue) -> syn::Result<(Vec<String>, Vec<String>)> {
+    let (mut key_vec, mut value_vec) = (vec![], vec![]);
+    for pair in query.value.split('&').filter(|pair| !pair.is_empty()) {
+        // A key without value like `pretty` is sent with an empty value.
+        let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+        if k.is_empty() {
             return Err(syn::Error::new(
-                proc_macro2::Span::call_site(),
-                format!("headers format is incorrect: {}", header_str),
+                query.span,
+                format!("query format is incorrect: {}", pair),
             ));
         }
-        let v = header_vec[1].trim().to_string();
-        if v.len() == 0 {
+        if is_dynamic(k) {
             return Err(syn::Error::new(
-                proc_macro2::Span::call_site(),
+                query.span,
+                format!("query key must not contain placeholders: {}", k),
+            ));
+        }
+        key_vec.push(k.to_string());
+        value_vec.push(v.to_string());
+    }
+    Ok((key_vec, value_vec))
+}
+
+fn parse_header_values(headers: &MetaValue) -> syn::Result<(Vec<String>, Vec<String>)> {
+    let (mut key_vec, mut value_vec) = (vec![], vec![]);
+    if headers.value.is_empty() {
+        return Ok((key_vec, value_vec));
+    }
+    for header_str in headers.value.split(';') {
+        let incorrect = || {
+            syn::Error::new(
+                headers.span,
                 format!("headers format is incorrect: {}", header_str),
+            )
+        };
+        // Split at the first colon only, values may contain colons like `${TOKEN:default}`.
+        let (k, v) = header_str.split_once(':').ok_or_else(incorrect)?;
+        let (k, v) = (k.trim(), v.trim());
+        if k.is_empty() || v.is_empty() {
+            return Err(incorrect());
+        }
+        if header_str.contains(['\r', '\n']) {
+            return Err(syn::Error::new(
+                headers.span,
+                format!("header must not contain CR or LF: {}", header_str.trim()),
             ));
         }
-        key_vec.push(k);
-        value_vec.push(v);
+        key_vec.push(k.to_string());
+        value_vec.push(v.to_string());
     }
-    return Ok((key_vec, value_vec));
+    Ok((key_vec, value_vec))
 }
 
 fn is_support_types(t: &str) -> bool {
-    return match t {
+    matches!(
+        t,
         "bool" | "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" | "f32" | "f64"
-        | "char" | "String" | "&str" => true,
-        _ => false,
-    };
+            | "char" | "String" | "&str"
+    )
 }
 
 fn is_support_struct(t: &str) -> bool {
-    if is_support_types(t) || is_sequences(t) {
-        false
-    } else {
-        true
-    }
+    !(is_support_types(t) || is_sequences(t))
 }
 
 fn is_sequences(t: &str) -> bool {
@@ -431,60 +1176,118 @@ fn is_sequences(t: &str) -> bool {
     false
 }
 
-fn get_body_fn_call(body_type: &syn::Type, body_var: &syn::Ident) -> proc_macro2::TokenStream {
-    let body_type_str = body_type.to_token_stream().to_string();
-    if body_type_str.ends_with("Vec < u8 >") {
-        return quote! {send_vec(#body_var)};
+/// The codec in the metadata, a name like `xml` or a path to a type implementing `Encoder` and `Decoder`.
+fn get_codec(codec: Option<&MetaValue>) -> syn::Result<proc_macro2::TokenStream> {
+    let codec = match codec {
+        Some(codec) => codec,
+        None => return Ok(quote! {feignhttp::codec::JsonCodec}),
     };
-    return if body_type_str.ends_with("String") || body_type_str.ends_with("& str") {
-        quote! {send_text(#body_var .to_string())}
-    } else {
-        quote! {send_json(& #body_var)}
+    if let Some((_, ty)) = CODECS.iter().find(|(name, _)| *name == codec.value) {
+        let ty = syn::Ident::new(ty, codec.span);
+        return Ok(quote! {feignhttp::codec::#ty});
+    }
+    let is_type = |path: &syn::Path| {
+        let name = path.segments.last().unwrap().ident.to_string();
+        name.starts_with(|c: char| c.is_ascii_uppercase())
     };
+    match syn::parse_str::<syn::Path>(&codec.value) {
+        Ok(path) if is_type(&path) => Ok(quote! {#path}),
+        _ => Err(syn::Error::new(
+            codec.span,
+            format!(
+                "unknown codec: {}, expected one of {} or a codec type",
+                codec.value,
+                CODECS.map(|(name, _)| name).join(", ")
+            ),
+        )),
+    }
 }
 
-fn get_return_fn(return_type: &syn::Type) -> proc_macro2::TokenStream {
-    let return_type_str = return_type.to_token_stream().to_string();
-    if return_type_str == "()" {
-        return quote! {none};
+/// The csv config of `csv_delimiter`, `csv_quote` and `csv_headers` metadata, `None` if none is set.
+fn get_csv_config(meta_map: &HashMap<String, MetaValue>) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let byte = |key: &str| -> syn::Result<Option<u8>> {
+        match meta_map.get(key) {
+            Some(value) if value.value.len() == 1 && value.value.is_ascii() => Ok(Some(value.value.as_bytes()[0])),
+            Some(value) => Err(syn::Error::new(
+                value.span,
+                format!("{} must be a single ASCII character", key),
+            )),
+            None => Ok(None),
+        }
+    };
+    let mut fields = Vec::new();
+    if let Some(delimiter) = byte("csv_delimiter")? {
+        fields.push(quote!(delimiter: #delimiter,));
     }
-    if return_type_str.ends_with("Vec < u8 >") {
-        return quote! {vec};
+    if let Some(quote) = byte("csv_quote")? {
+        fields.push(quote!(quote: #quote,));
     }
-    let is_text = if return_type_str.ends_with("String") {
-        true
-    } else {
-        false
-    };
-    return if is_text {
-        quote! {text}
-    } else {
-        quote! {json}
+    if let Some(value) = meta_map.get("csv_headers") {
+        let has_headers = match value.value.as_str() {
+            "true" => true,
+            "false" => false,
+            _ => return Err(syn::Error::new(value.span, "csv_headers must be true or false")),
+        };
+        fields.push(quote!(has_headers: #has_headers,));
+    }
+    if fields.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(quote! {
+        feignhttp::CsvConfig { #(#fields)* ..::std::default::Default::default() }
+    }))
+}
+
+/// Send a body in the format of the attribute, or select `IntoBody` or the codec by the type.
+fn get_body_fn_call(body_arg: &FnArg, codec: &proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    let body_var = &body_arg.var;
+    let format = match &body_arg.format {
+        Some(format) => format,
+        None => return Ok(quote! {send_into_body(util::Encode(#body_var, #codec).body())}),
     };
+    match format.to_string().as_str() {
+        "json" => Ok(quote! {send_json(& #body_var)}),
+        "text" => Ok(quote! {send_text(#body_var.to_string())}),
+        "bytes" => Ok(quote! {send_vec(::std::convert::Into::<Vec<u8>>::into(#body_var))}),
+        name => match BODY_FORMATS.iter().find_map(|(n, wrapper)| wrapper.filter(|_| *n == name)) {
+            Some(wrapper) => {
+                let wrapper = syn::Ident::new(wrapper, format.span());
+                Ok(quote! {send_into_body(feignhttp::#wrapper(#body_var))})
+            }
+            None => Err(syn::Error::new_spanned(
+                format,
+                format!(
+                    "unknown body format: {}, expected one of {}",
+                    format,
+                    BODY_FORMATS.map(|(name, _)| name).join(", ")
+                ),
+            )),
+        },
+    }
 }
 
 fn get_form_fn_call(
-    form_names: &Vec<String>,
-    form_types: &Vec<syn::Type>,
-    form_vars: &Vec<syn::Ident>,
+    form_names: &[String],
+    form_types: &[syn::Type],
+    form_vars: &[syn::Ident],
 ) -> Result<proc_macro2::TokenStream, String> {
     if form_names.is_empty() {
         return Err("no form parameters".to_string());
     }
-    return if form_names.len() == 1 {
-        let form_name = form_names.get(0).unwrap();
-        let form_type = form_types.get(0).unwrap();
-        let form_var = form_vars.get(0).unwrap();
+    if form_names.len() == 1 {
+        let form_name = &form_names[0];
+        let form_type = &form_types[0];
+        let form_var = &form_vars[0];
         match form_type {
             syn::Type::Path(t) => {
                 let ty = t.to_token_stream().to_string();
                 if is_support_types(&ty) {
                     let mut token_str = "send_form(&vec![".to_string();
-                    token_str.push_str("(");
+                    token_str.push('(');
                     token_str.push_str(&format!(
                         "\"{}\", format!(\"{{}}\", {})",
                         form_name,
-                        form_var.to_string()
+                        form_var
                     ));
                     token_str.push_str("),");
                     token_str.push_str("])");
@@ -496,16 +1299,17 @@ fn get_form_fn_call(
             syn::Type::Reference(t) => {
                 let ty = t.to_token_stream().to_string();
                 if is_support_types(&ty.replace(" ", "").replace("&", "")) {
-                    return Err(format!(
+                    return Err(
                         "one form parameter only supports scalar types, &str, String or struct"
-                    ));
+                            .to_string(),
+                    );
                 } else if ty.contains("& str") {
                     let mut token_str = "send_form(&vec![".to_string();
-                    token_str.push_str("(");
+                    token_str.push('(');
                     token_str.push_str(&format!(
                         "\"{}\", format!(\"{{}}\", {})",
                         form_name,
-                        form_var.to_string()
+                        form_var
                     ));
                     token_str.push_str("),");
                     token_str.push_str("])");
@@ -527,19 +1331,20 @@ fn get_form_fn_call(
             let form_var = form_vars.get(i).unwrap();
             let ty = form_type.to_token_stream().to_string().replace(" ", "");
             if !is_support_types(&ty) {
-                return Err(format!(
+                return Err(
                     "two or more form parameters only supports scalar types, &str or String"
-                ));
+                        .to_string(),
+                );
             }
-            token_str.push_str("(");
+            token_str.push('(');
             token_str.push_str(&format!(
                 "\"{}\", format!(\"{{}}\", {})",
                 form_name,
-                form_var.to_string()
+                form_var
             ));
             token_str.push_str("),");
         }
         token_str.push_str("])");
         Ok(proc_macro2::TokenStream::from_str(token_str.as_str()).unwrap())
-    };
+    }
 }
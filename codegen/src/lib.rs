@@ -1,14 +1,15 @@
 mod enu;
 mod func;
+mod meta;
 mod structure;
 mod util;
 
 use enu::Method;
-use func::http_impl;
+use func::{http_impl, paginate_attr_impl};
 use proc_macro::TokenStream;
 use structure::{feign_client_impl, feign_impl};
 
-#[proc_macro_derive(Feign, attributes(url_path, query, header, param))]
+#[proc_macro_derive(Feign, attributes(url_path, query, header, param, cookie, cookie_jar))]
 pub fn feign_client(item: TokenStream) -> TokenStream {
     feign_client_impl(item)
 }
@@ -37,3 +38,8 @@ pub fn put(attr: TokenStream, item: TokenStream) -> TokenStream {
 pub fn delete(attr: TokenStream, item: TokenStream) -> TokenStream {
     http_impl(Method::DELETE, attr, item)
 }
+
+#[proc_macro_attribute]
+pub fn paginate(attr: TokenStream, item: TokenStream) -> TokenStream {
+    paginate_attr_impl(attr, item)
+}
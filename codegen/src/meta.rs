@@ -0,0 +1,201 @@
+use proc_macro2::Span;
+use quote::{quote, ToTokens};
+use std::collections::HashMap;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, Lit, LitStr, Token};
+
+/// Keys of the metadata assigned by a literal, like `headers = "accept: text/plain"`.
+pub const META_KEYS: [&str; 16] = [
+    "headers", "query", "body", "content_type", "consumes", "produces", "codec", "profile", "config",
+    "connect_timeout", "timeout", "compress", "cache", "csv_delimiter", "csv_quote", "csv_headers",
+];
+
+/// Keys of the profile metadata, `config` is an alias of `profile`.
+pub const PROFILE_KEYS: [&str; 2] = ["profile", "config"];
+
+/// A literal metadata value with the span of the literal.
+#[derive(Clone)]
+pub struct MetaValue {
+    pub value: String,
+    pub span: Span,
+}
+
+impl ToTokens for MetaValue {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        LitStr::new(&self.value, self.span).to_tokens(tokens)
+    }
+}
+
+/// One argument of the attribute, `key = value` or a positional expression.
+enum MetaArg {
+    Assign(Ident, Expr),
+    Positional(Expr),
+}
+
+impl Parse for MetaArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value: Expr = input.parse()?;
+            return Ok(MetaArg::Assign(key, value));
+        }
+        Ok(MetaArg::Positional(input.parse()?))
+    }
+}
+
+/// Metadata of a request attribute like `#[get("http://xxx", path = "/xxx", timeout = 3000)]`.
+///
+/// The first argument can be positional, it is the url of a function or `#[feign]`,
+/// and the path of a method in `#[feign]`.
+#[derive(Default)]
+pub struct AttrMeta {
+    pub first: Option<Expr>,
+    pub url: Option<Expr>,
+    pub path: Option<Expr>,
+    pub metas: HashMap<String, MetaValue>,
+}
+
+impl Parse for AttrMeta {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let args = Punctuated::<MetaArg, Token![,]>::parse_terminated(input)?;
+        let mut meta = AttrMeta::default();
+        for (i, arg) in args.into_iter().enumerate() {
+            let (key, value) = match arg {
+                MetaArg::Positional(expr) if i == 0 => {
+                    meta.first = Some(expr);
+                    continue;
+                }
+                MetaArg::Positional(expr) => {
+                    return Err(syn::Error::new_spanned(
+                        expr,
+                        "expected metadata like `key = value`",
+                    ))
+                }
+                MetaArg::Assign(key, value) => (key, value),
+            };
+            let name = key.to_string();
+            let duplicated = match name.as_str() {
+                "url" => meta.url.replace(value).is_some(),
+                "path" => meta.path.replace(value).is_some(),
+                _ if META_KEYS.contains(&name.as_str()) => {
+                    let value = lit_value(&name, &value)?;
+                    meta.metas.insert(name.clone(), value).is_some()
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        key,
+                        format!("unknown metadata key: {}", name),
+                    ))
+                }
+            };
+            if duplicated {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    format!("duplicated metadata key: {}", name),
+                ));
+            }
+        }
+        Ok(meta)
+    }
+}
+
+impl AttrMeta {
+    /// Parse the metadata of `#[get(...)]` or `#[feign(...)]`, the url is required unless a profile is specified.
+    pub fn parse_with_url(attr: proc_macro::TokenStream) -> syn::Result<Self> {
+        if attr.is_empty() {
+            return Err(syn::Error::new(Span::call_site(), "no metadata assign"));
+        }
+        let mut meta = syn::parse::<AttrMeta>(attr)?;
+        if let Some(first) = meta.first.take() {
+            if let Some(url) = &meta.url {
+                return Err(syn::Error::new_spanned(url, "duplicated metadata key: url"));
+            }
+            meta.url = Some(first);
+        }
+        match &meta.url {
+            Some(url) => validate_url(url)?,
+            None if !meta.has_profile() => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "metadata url not specified",
+                ))
+            }
+            None => {}
+        }
+        Ok(meta)
+    }
+
+    /// Parse the metadata of a method in `#[feign]`, like `#[get("/xxx")]`, the positional argument is the path.
+    pub fn parse_method(attr: &syn::Attribute) -> syn::Result<Self> {
+        if attr.tokens.is_empty() {
+            return Ok(AttrMeta::default());
+        }
+        let mut meta = attr.parse_args::<AttrMeta>()?;
+        if let Some(url) = &meta.url {
+            return Err(syn::Error::new_spanned(
+                url,
+                "metadata url is specified by `#[feign]`, use path instead",
+            ));
+        }
+        if let Some(first) = meta.first.take() {
+            if let Some(path) = &meta.path {
+                return Err(syn::Error::new_spanned(path, "duplicated metadata key: path"));
+            }
+            meta.path = Some(first);
+        }
+        Ok(meta)
+    }
+
+    pub fn has_profile(&self) -> bool {
+        PROFILE_KEYS.iter().any(|k| self.metas.contains_key(*k))
+    }
+
+    /// The url token stream, it can be retrieved by a variable.
+    pub fn url_stream(&self) -> Option<proc_macro2::TokenStream> {
+        self.url.as_ref().map(|url| quote!(#url))
+    }
+
+    /// The path token stream, it can be retrieved by a variable.
+    pub fn path_stream(&self) -> Option<proc_macro2::TokenStream> {
+        self.path.as_ref().map(|path| quote!(#path))
+    }
+}
+
+/// A URL can be a literal, a constant or a field expression.
+fn validate_url(url: &Expr) -> syn::Result<()> {
+    match url {
+        Expr::Lit(syn::ExprLit { lit: Lit::Str(_), .. }) | Expr::Path(_) | Expr::Field(_) => Ok(()),
+        _ => Err(syn::Error::new_spanned(url, "metadata url is invalid")),
+    }
+}
+
+/// Get the value of a metadata assigned by a literal.
+fn lit_value(key: &str, value: &Expr) -> syn::Result<MetaValue> {
+    let lit = match value {
+        Expr::Lit(expr_lit) => &expr_lit.lit,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                value,
+                format!("metadata {} must be a literal", key),
+            ))
+        }
+    };
+    let value = match lit {
+        Lit::Str(s) => s.value(),
+        Lit::Int(i) => i.base10_digits().to_string(),
+        Lit::Float(f) => f.base10_digits().to_string(),
+        Lit::Bool(b) => b.value.to_string(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                lit,
+                format!("metadata {} must be a string, number or bool", key),
+            ))
+        }
+    };
+    Ok(MetaValue {
+        value,
+        span: lit.span(),
+    })
+}
@@ -1,8 +1,6 @@
 use crate::enu::Method;
 use crate::func::{client_fn_impl, fn_impl, FnMetadata};
-use crate::util::{
-    get_meta_str_value, get_metas, parse_exprs, parse_url_stream, remove_url_attr,
-};
+use crate::meta::{AttrMeta, MetaValue, PROFILE_KEYS};
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
 use std::collections::HashMap;
@@ -10,16 +8,15 @@ use syn::DeriveInput;
 use syn::{parse_macro_input, ItemImpl};
 
 pub fn feign_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let url = match parse_url_stream(&attr) {
-        Ok(url) => url,
+    let meta = match AttrMeta::parse_with_url(attr) {
+        Ok(meta) => meta,
         Err(err) => return err.into_compile_error().into(),
     };
 
-    let meta_map = parse_exprs(&remove_url_attr(&attr.to_string()));
     let item_impl = parse_macro_input!(item as ItemImpl);
     let impl_signature = impl_signature(&item_impl);
 
-    let fn_streams = match fn_to_streams(url, item_impl.items, meta_map) {
+    let fn_streams = match fn_to_streams(meta, item_impl.items) {
         Ok(streams) => streams,
         Err(err) => return err.into_compile_error().into(),
     };
@@ -73,17 +70,19 @@ fn impl_signature(item_impl: &ItemImpl) -> proc_macro2::TokenStream {
 }
 
 fn fn_to_streams(
-    url: proc_macro2::TokenStream,
+    meta: AttrMeta,
     items: Vec<syn::ImplItem>,
-    meta_map: HashMap<String, String>,
 ) -> syn::Result<Vec<proc_macro2::TokenStream>> {
-    let base_url = url;
-    let base_meta = meta_map;
+    let base_url = meta.url_stream();
+    let base_paths: Vec<proc_macro2::TokenStream> = meta.path_stream().into_iter().collect();
+    let base_meta: HashMap<String, MetaValue> = meta.metas;
     let mut method_streams = Vec::new();
     for item in items.iter() {
         if let syn::ImplItem::Method(syn::ImplItemMethod { attrs, .. }) = item {
-            if let Some(attr) = attrs.last() {
-                let mut url = base_url.clone();
+            // `#[paginate]` is handled with the method attribute.
+            if let Some(attr) = attrs.iter().rev().find(|attr| !attr.path.is_ident("paginate")) {
+                let url = base_url.clone();
+                let mut paths = base_paths.clone();
                 let mut meta_map = base_meta.clone();
                 let method_ident =
                     Method::from_str(&attr.path.segments.last().unwrap().ident.to_string());
@@ -92,20 +91,31 @@ fn fn_to_streams(
                     Err(err) => return Err(syn::Error::new_spanned(&attr.path, err)),
                 };
 
-                let fn_path = parse_fn_path(attr)?;
-                if !fn_path.is_empty() {
-                    url = quote!(#url + #fn_path);
+                let fn_meta = AttrMeta::parse_method(attr)?;
+                if let Some(fn_path) = fn_meta.path_stream() {
+                    paths.push(fn_path);
                 }
 
                 // Override meta.
-                let map = parse_fn_metas(attr);
-                for (k, v) in map {
+                for (k, v) in fn_meta.metas {
+                    // A profile of the method replaces the profile of the struct, whichever alias is used.
+                    if PROFILE_KEYS.contains(&k.as_str()) {
+                        meta_map.retain(|k, _| !PROFILE_KEYS.contains(&k.as_str()));
+                    }
+                    // Query of the method is appended to the query of the struct.
+                    if k == "query" {
+                        if let Some(query) = meta_map.get_mut("query") {
+                            query.value = format!("{}&{}", query.value, v.value);
+                            continue;
+                        }
+                    }
                     meta_map.insert(k, v);
                 }
 
                 let fn_stream = fn_impl(
                     FnMetadata {
                         url,
+                        paths,
                         method,
                         meta_map,
                     },
@@ -121,59 +131,3 @@ fn fn_to_streams(
     }
     Ok(method_streams)
 }
-
-fn parse_fn_path(attr: &syn::Attribute) -> syn::Result<proc_macro2::TokenStream> {
-    if let Some(vec) = get_metas(attr) {
-        if let Some(nested_meta) = vec.first() {
-            match nested_meta {
-                // A literal, like the `"/xxx"` in `#[get("/xxx")]`.
-                syn::NestedMeta::Lit(lit) => {
-                    if let syn::Lit::Str(lit) = lit {
-                        return Ok(lit.value().to_token_stream());
-                    }
-                }
-                _ => {
-                    return match get_meta_str_value(nested_meta, "path") {
-                        Some(val) => Ok(val.to_token_stream()),
-                        None => Err(syn::Error::new_spanned(
-                            nested_meta,
-                            "metadata path not specified or must be the first",
-                        )),
-                    }
-                }
-            }
-        }
-    }
-    Ok(proc_macro2::TokenStream::new())
-}
-
-fn parse_fn_metas(attr: &syn::Attribute) -> HashMap<String, String> {
-    let mut attr_map = HashMap::new();
-    if let Some(metas) = get_metas(attr) {
-        for meta in metas.into_iter() {
-            match meta {
-                // A literal, like the `xxx` in `#[get(p = xxx)]`.
-                syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) => {
-                    let key = name_value.path.segments.last().unwrap().ident.to_string();
-                    match name_value.lit {
-                        syn::Lit::Str(s) => {
-                            attr_map.insert(key, s.value());
-                        }
-                        syn::Lit::Int(i) => {
-                            attr_map.insert(key, i.to_string());
-                        }
-                        syn::Lit::Float(f) => {
-                            attr_map.insert(key, f.to_string());
-                        }
-                        syn::Lit::Bool(b) => {
-                            attr_map.insert(key, format!("{}", b.value()));
-                        }
-                        _ => {}
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-    attr_map
-}
@@ -5,6 +5,16 @@ fn test_func() {
     t.compile_fail("tests/ui/func/no_url.rs");
     t.compile_fail("tests/ui/func/no_url2.rs");
     t.compile_fail("tests/ui/func/invalid_url.rs");
+    t.compile_fail("tests/ui/func/unknown_key.rs");
+    t.compile_fail("tests/ui/func/duplicated_key.rs");
+    t.compile_fail("tests/ui/func/positional.rs");
+    t.compile_fail("tests/ui/func/literal_value.rs");
+    t.compile_fail("tests/ui/func/headers.rs");
+    t.compile_fail("tests/ui/func/query.rs");
+    t.compile_fail("tests/ui/func/timeout.rs");
+    t.compile_fail("tests/ui/func/compress.rs");
+    t.compile_fail("tests/ui/func/placeholder.rs");
+    t.compile_fail("tests/ui/func/path_arg.rs");
     t.compile_fail("tests/ui/func/async.rs");
     t.compile_fail("tests/ui/func/return_value.rs");
     t.compile_fail("tests/ui/func/return_value2.rs");
@@ -13,6 +23,17 @@ fn test_func() {
     t.compile_fail("tests/ui/func/form2.rs");
     t.compile_fail("tests/ui/func/body.rs");
     t.compile_fail("tests/ui/func/body_form.rs");
+    t.compile_fail("tests/ui/func/body_template.rs");
+    t.compile_fail("tests/ui/func/body_format.rs");
+    t.compile_fail("tests/ui/func/codec.rs");
+    t.compile_fail("tests/ui/func/csv.rs");
+    t.compile_fail("tests/ui/func/csv2.rs");
+    t.compile_fail("tests/ui/func/paginate.rs");
+    t.compile_fail("tests/ui/func/paginate2.rs");
+    t.compile_fail("tests/ui/func/download.rs");
+    t.compile_fail("tests/ui/func/download2.rs");
+    t.compile_fail("tests/ui/func/progress.rs");
+    t.compile_fail("tests/ui/func/cache.rs");
 }
 
 #[test]
@@ -22,5 +43,7 @@ fn test_struct() {
     t.compile_fail("tests/ui/struct/no_url.rs");
     t.compile_fail("tests/ui/struct/no_url2.rs");
     t.compile_fail("tests/ui/struct/method.rs");
-    t.compile_fail("tests/ui/struct/path.rs");
+    t.compile_fail("tests/ui/struct/unknown_key.rs");
+    t.compile_fail("tests/ui/struct/method_url.rs");
+    t.compile_fail("tests/ui/struct/path_arg.rs");
 }
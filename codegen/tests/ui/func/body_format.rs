@@ -0,0 +1,10 @@
+use feignhttp_codegen::post;
+
+// error: unknown body format: yaml, expected one of json, text, bytes, msgpack, cbor, protobuf
+//    |  async fn send_post(#[body(yaml)] text: String) -> feignhttp::Result<String> {}
+//    |                            ^^^^
+
+#[post("http://xxx")]
+async fn send_post(#[body(yaml)] text: String) -> feignhttp::Result<String> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::post;
+
+// error: placeholder {id} in body has no matching #[param] argument
+//    |  #[post("http://xxx", body = r#"{"id":{id}}"#)]
+//    |                              ^^^^^^^^^^^^^^^^
+
+#[post("http://xxx", body = r#"{"id":{id}}"#)]
+async fn send_post() -> feignhttp::Result<String> {}
+
+fn main() {}
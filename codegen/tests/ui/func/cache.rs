@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: unsupported cache: redis, expected memory, disk or disk:<dir>
+//    |  #[get("http://xxx", cache = "redis")]
+//    |                              ^^^^^^^
+
+#[get("http://xxx", cache = "redis")]
+async fn get() -> feignhttp::Result<String> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: unknown codec: yaml, expected one of json, xml, msgpack, cbor or a codec type
+//    |  #[get("http://xxx", codec = "yaml")]
+//    |                              ^^^^^^
+
+#[get("http://xxx", codec = "yaml")]
+async fn get() -> feignhttp::Result<String> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::post;
+
+// error: unsupported compress encoding: lzma, expected one of gzip, deflate, br
+//    |  #[post("http://xxx", compress = "lzma")]
+//    |                                  ^^^^^^
+
+#[post("http://xxx", compress = "lzma")]
+async fn send_post(#[body] text: String) -> feignhttp::Result<String> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: csv_delimiter must be a single ASCII character
+//    |  #[get("http://xxx", csv_delimiter = "||")]
+//    |                                      ^^^^
+
+#[get("http://xxx", csv_delimiter = "||")]
+async fn get() -> feignhttp::Result<String> {}
+
+fn main() {}
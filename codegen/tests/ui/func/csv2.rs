@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: csv_headers must be true or false
+//    |  #[get("http://xxx", csv_headers = "yes")]
+//    |                                    ^^^^^
+
+#[get("http://xxx", csv_headers = "yes")]
+async fn get() -> feignhttp::Result<String> {}
+
+fn main() {}
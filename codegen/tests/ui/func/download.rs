@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: download must return `feignhttp::Result<feignhttp::Download>`
+//    |  async fn get(#[download] dest: &str) -> feignhttp::Result<String> {}
+//    |                                       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+#[get("http://xxx")]
+async fn get(#[download] dest: &str) -> feignhttp::Result<String> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::post;
+
+// error: download does not support a request body
+//    |  async fn post(#[download] dest: &str, #[body] text: String) -> feignhttp::Result<feignhttp::Download> {}
+//    |                            ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+#[post("http://xxx")]
+async fn post(#[download] dest: &str, #[body] text: String) -> feignhttp::Result<feignhttp::Download> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: duplicated metadata key: timeout
+//    |  #[get("http://xxx", timeout = 3000, timeout = 5000)]
+//    |                                      ^^^^^^^
+
+#[get("http://xxx", timeout = 3000, timeout = 5000)]
+async fn send_get() {}
+
+fn main() {}
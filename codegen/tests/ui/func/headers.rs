@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: headers format is incorrect: accept
+//    |  #[get("http://xxx", headers = "accept")]
+//    |                                ^^^^^^^^
+
+#[get("http://xxx", headers = "accept")]
+async fn send_get() {}
+
+fn main() {}
@@ -2,7 +2,7 @@ use feignhttp_codegen::get;
 
 // error: metadata url is invalid
 //    |  #[get(url + "/aaa")]
-//    |  ^^^^^^^^^^^^^^^^^^^^
+//    |        ^^^^^^^^^^^^
 
 const url: &str = "http://xxx";
 
@@ -0,0 +1,12 @@
+use feignhttp_codegen::get;
+
+// error: metadata timeout must be a literal
+//    |  #[get("http://xxx", timeout = TIMEOUT)]
+//    |                                ^^^^^^^
+
+const TIMEOUT: u64 = 3000;
+
+#[get("http://xxx", timeout = TIMEOUT)]
+async fn send_get() {}
+
+fn main() {}
@@ -1,10 +1,10 @@
 use feignhttp_codegen::get;
 
 // error: metadata url not specified
-//    |  #[get(aaa = "http://xxx")]
-//    |  ^^^^^^^^^^^^^^^^^^^^^^^^^^
+//    |  #[get(timeout = 3000)]
+//    |  ^^^^^^^^^^^^^^^^^^^^^^
 
-#[get(aaa = "http://xxx")]
+#[get(timeout = 3000)]
 fn send_get() {}
 
 fn main() {}
@@ -0,0 +1,11 @@
+use feignhttp_codegen::get;
+
+// error: paginate must have one of link_header, cursor or page
+//    |  #[paginate(link_header, page = "page")]
+//    |  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+#[get("http://xxx")]
+#[paginate(link_header, page = "page")]
+async fn get() -> feignhttp::Result<Vec<String>> {}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use feignhttp_codegen::get;
+
+// error: paginate must return a list like `feignhttp::Result<Vec<T>>`
+//    |  async fn get() -> feignhttp::Result<String> {}
+//    |                                      ^^^^^^
+
+#[get("http://xxx")]
+#[paginate(link_header)]
+async fn get() -> feignhttp::Result<String> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: #[path] argument repo has no matching placeholder in url
+//    |  async fn repo(#[path] owner: &str, #[path] repo: &str) -> feignhttp::Result<String> {}
+//    |                                             ^^^^^^^^^^
+
+#[get("http://xxx", path = "/repos/{owner}")]
+async fn repo(#[path] owner: &str, #[path] repo: &str) -> feignhttp::Result<String> {}
+
+fn main() {}
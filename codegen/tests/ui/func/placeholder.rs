@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: placeholder {repo} has no matching #[path] or #[param] argument
+//    |  #[get("http://xxx", path = "/repos/{owner}/{repo}")]
+//    |                             ^^^^^^^^^^^^^^^^^^^^^^^
+
+#[get("http://xxx", path = "/repos/{owner}/{repo}")]
+async fn repo(#[path] owner: &str) -> feignhttp::Result<String> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: expected metadata like `key = value`
+//    |  #[get("http://xxx", "/aaa")]
+//    |                      ^^^^^^
+
+#[get("http://xxx", "/aaa")]
+async fn send_get() {}
+
+fn main() {}
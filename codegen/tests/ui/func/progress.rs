@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: request must have only one progress
+//    |  async fn get(#[progress] a: impl Fn(feignhttp::Progress), #[progress] b: impl Fn(feignhttp::Progress)) -> feignhttp::Result<String> {}
+//    |                           ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+#[get("http://xxx")]
+async fn get(#[progress] a: impl Fn(feignhttp::Progress), #[progress] b: impl Fn(feignhttp::Progress)) -> feignhttp::Result<String> {}
+
+fn main() {}
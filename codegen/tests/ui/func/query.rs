@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: query key must not contain placeholders: {key}
+//    |  #[get("http://xxx", query = "{key}=1")]
+//    |                              ^^^^^^^^^
+
+#[get("http://xxx", query = "{key}=1")]
+async fn send_get(#[param] key: &str) -> feignhttp::Result<String> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: invalid duration: abc
+//    |  #[get("http://xxx", timeout = "abc")]
+//    |                                ^^^^^
+
+#[get("http://xxx", timeout = "abc")]
+async fn send_get() -> feignhttp::Result<String> {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use feignhttp_codegen::get;
+
+// error: unknown metadata key: aaa
+//    |  #[get("http://xxx", aaa = "/aaa")]
+//    |                      ^^^
+
+#[get("http://xxx", aaa = "/aaa")]
+async fn send_get() {}
+
+fn main() {}
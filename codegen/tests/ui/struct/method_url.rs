@@ -0,0 +1,15 @@
+use feignhttp_codegen::feign;
+
+// error: metadata url is specified by `#[feign]`, use path instead
+//    |  #[get(url = "http://yyy")]
+//    |              ^^^^^^^^^^^^
+
+struct Http;
+
+#[feign("http://xxx")]
+impl Http {
+    #[get(url = "http://yyy")]
+    pub async fn get() -> Result<String> {}
+}
+
+fn main() {}
@@ -1,12 +1,12 @@
 use feignhttp_codegen::feign;
 
 // error: metadata url not specified
-//    |  #[feign(aaa = "http://xxx")]
-//    |  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+//    |  #[feign(timeout = 3000)]
+//    |  ^^^^^^^^^^^^^^^^^^^^^^^^
 
 struct Http;
 
-#[feign(aaa = "http://xxx")]
+#[feign(timeout = 3000)]
 impl Http {}
 
 fn main() {}
@@ -0,0 +1,15 @@
+use feignhttp_codegen::feign;
+
+// error: #[path] argument id has no matching placeholder in url
+//    |  pub async fn user(&self, #[path] id: u32) -> feignhttp::Result<String> {}
+//    |                                   ^^^^^^^
+
+struct Http;
+
+#[feign("http://xxx/{tenant}")]
+impl Http {
+    #[get("/users")]
+    pub async fn user(&self, #[path] id: u32) -> feignhttp::Result<String> {}
+}
+
+fn main() {}
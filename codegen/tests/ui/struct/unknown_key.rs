@@ -1,8 +1,8 @@
 use feignhttp_codegen::feign;
 
-// error: metadata path not specified or must be the first
+// error: unknown metadata key: aaa
 //    |  #[get(aaa = "/aaa")]
-//    |        ^^^^^^^^^^^^
+//    |        ^^^
 
 struct Http;
 
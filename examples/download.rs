@@ -0,0 +1,18 @@
+use feignhttp::{get, Download};
+
+// The file name is taken from the `Content-Disposition` header when the destination is a directory.
+#[get("https://github.com/{owner}/{repo}/archive/refs/heads/{branch}.zip")]
+async fn archive(
+    #[path] owner: &str,
+    #[path] repo: &str,
+    #[path] branch: &str,
+    #[download] dest: &std::path::Path,
+) -> feignhttp::Result<Download> {}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let download = archive("dxx", "feignhttp", "main", &std::env::temp_dir()).await?;
+    println!("downloaded {} bytes to {}", download.len, download.path.display());
+
+    Ok(())
+}
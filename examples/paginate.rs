@@ -0,0 +1,39 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use feignhttp::{get, paginate};
+use futures_util::StreamExt;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Contributor {
+    pub login: String,
+    pub contributions: u32,
+}
+
+#[cfg(feature = "json")]
+const GITHUB_URL: &str = "https://api.github.com";
+
+// Pages are requested by the `rel="next"` url of the `Link` header.
+#[cfg(feature = "json")]
+#[paginate(link_header, max_pages = 5)]
+#[get(url = GITHUB_URL, path = "/repos/{owner}/{repo}/contributors")]
+async fn contributors(
+    #[path] owner: &str,
+    #[path] repo: &str,
+    per_page: u32,
+) -> feignhttp::Result<Vec<Contributor>> {}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "json")]
+    {
+        let mut contributors = contributors("rust-lang", "log", 30);
+        while let Some(contributor) = contributors.next().await {
+            let contributor = contributor?;
+            println!("{}: {}", contributor.login, contributor.contributions);
+        }
+    }
+
+    Ok(())
+}
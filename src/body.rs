@@ -0,0 +1,274 @@
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+use crate::codec::{self, Decoder, Encoder};
+use crate::error::Result;
+use crate::http::HttpResponse;
+use crate::ResponseWrapper;
+use async_trait::async_trait;
+use std::borrow::Cow;
+use std::ops::Deref;
+
+/// A type which can be sent as a request body, like `#[body] data: T`.
+///
+/// A body type without `IntoBody` is sent as JSON.
+pub trait IntoBody {
+    /// The content type of the body, a `content-type` header takes precedence.
+    fn content_type(&self) -> &str;
+
+    fn into_body(self) -> Result<Vec<u8>>;
+}
+
+/// A type which can be received from a response, like `feignhttp::Result<T>`.
+///
+/// A return type without `FromResponse` is received as JSON.
+#[async_trait]
+pub trait FromResponse: Sized {
+    /// The media type sent by the `accept` header and expected in the response `content-type`.
+    fn accept() -> Option<&'static str> {
+        None
+    }
+
+    async fn from_response(response: ResponseWrapper) -> Result<Self>;
+}
+
+/// A JSON body or response, serialized and deserialized by serde.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Json<T>(pub T);
+
+/// A MessagePack body or response, serialized and deserialized by serde.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MsgPack<T>(pub T);
+
+/// A CBOR body or response, serialized and deserialized by serde.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cbor<T>(pub T);
+
+/// A Protobuf body or response, encoded and decoded by prost.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Protobuf<T>(pub T);
+
+/// A plain text body or response.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Text(pub String);
+
+/// A binary body or response.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Text {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl Bytes {
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+macro_rules! wrapper {
+    ($($name:ident),*) => {
+        $(
+            impl<T> $name<T> {
+                pub fn into_inner(self) -> T {
+                    self.0
+                }
+            }
+
+            impl<T> Deref for $name<T> {
+                type Target = T;
+
+                fn deref(&self) -> &T {
+                    &self.0
+                }
+            }
+        )*
+    };
+}
+
+wrapper!(Json, MsgPack, Cbor, Protobuf);
+
+impl Deref for Text {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(bytes: Bytes) -> Vec<u8> {
+        bytes.0
+    }
+}
+
+/// Implement `IntoBody` and `FromResponse` for a wrapper by a codec.
+macro_rules! codec_body {
+    ($feature:literal, $name:ident, $codec:ident) => {
+        #[cfg(feature = $feature)]
+        impl<T: serde::Serialize> IntoBody for $name<T> {
+            fn content_type(&self) -> &str {
+                codec::$codec.content_type()
+            }
+
+            fn into_body(self) -> Result<Vec<u8>> {
+                codec::$codec.encode(&self.0)
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        #[async_trait]
+        impl<T: serde::de::DeserializeOwned> FromResponse for $name<T> {
+            fn accept() -> Option<&'static str> {
+                Some(codec::$codec.accept())
+            }
+
+            async fn from_response(response: ResponseWrapper) -> Result<Self> {
+                let body = response.vec().await?;
+                codec::$codec.decode(&body).map($name)
+            }
+        }
+    };
+}
+
+codec_body!("json", Json, JsonCodec);
+codec_body!("msgpack", MsgPack, MsgPackCodec);
+codec_body!("cbor", Cbor, CborCodec);
+
+#[cfg(feature = "protobuf")]
+impl<T: prost::Message> IntoBody for Protobuf<T> {
+    fn content_type(&self) -> &str {
+        "application/x-protobuf"
+    }
+
+    fn into_body(self) -> Result<Vec<u8>> {
+        Ok(self.0.encode_to_vec())
+    }
+}
+
+impl IntoBody for Text {
+    fn content_type(&self) -> &str {
+        "text/plain"
+    }
+
+    fn into_body(self) -> Result<Vec<u8>> {
+        Ok(self.0.into_bytes())
+    }
+}
+
+impl IntoBody for Bytes {
+    fn content_type(&self) -> &str {
+        "application/octet-stream"
+    }
+
+    fn into_body(self) -> Result<Vec<u8>> {
+        Ok(self.0)
+    }
+}
+
+macro_rules! text_body {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoBody for $ty {
+                fn content_type(&self) -> &str {
+                    "text/plain"
+                }
+
+                fn into_body(self) -> Result<Vec<u8>> {
+                    Ok(self.to_string().into_bytes())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! bytes_body {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoBody for $ty {
+                fn content_type(&self) -> &str {
+                    "application/octet-stream"
+                }
+
+                fn into_body(self) -> Result<Vec<u8>> {
+                    Ok(self.to_vec())
+                }
+            }
+        )*
+    };
+}
+
+text_body!(String, &String, &str, Box<str>, Cow<'_, str>);
+bytes_body!(Vec<u8>, &Vec<u8>, &[u8]);
+
+#[cfg(feature = "protobuf")]
+#[async_trait]
+impl<T: prost::Message + Default> FromResponse for Protobuf<T> {
+    fn accept() -> Option<&'static str> {
+        Some("application/x-protobuf, application/protobuf")
+    }
+
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        let body = response.vec().await?;
+        T::decode(body.as_slice())
+            .map(Protobuf)
+            .map_err(crate::error::Error::decode)
+    }
+}
+
+#[async_trait]
+impl FromResponse for Text {
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        response.text().await.map(Text)
+    }
+}
+
+#[async_trait]
+impl FromResponse for Bytes {
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        response.vec().await.map(Bytes)
+    }
+}
+
+#[async_trait]
+impl FromResponse for String {
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        response.text().await
+    }
+}
+
+#[async_trait]
+impl FromResponse for Box<str> {
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        response.text().await.map(String::into_boxed_str)
+    }
+}
+
+#[async_trait]
+impl FromResponse for Cow<'static, str> {
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        response.text().await.map(Cow::Owned)
+    }
+}
+
+#[async_trait]
+impl FromResponse for Vec<u8> {
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        response.vec().await
+    }
+}
+
+#[async_trait]
+impl FromResponse for () {
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        response.none().await
+    }
+}
@@ -0,0 +1,315 @@
+use crate::error::{Error, Result};
+use crate::http::{HttpRequest, HttpResponse, Replay};
+use crate::{RequestWrapper, ResponseWrapper};
+use http::header::{
+    HeaderMap, HeaderName, HeaderValue, AGE, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH, DATE, ETAG, EXPIRES,
+    LAST_MODIFIED, TRANSFER_ENCODING, VARY,
+};
+use http::StatusCode;
+use once_cell::sync::Lazy;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The maximum number of responses in the memory cache, the oldest response is removed first.
+const MAX_MEMORY_ENTRIES: usize = 1000;
+
+/// Responses cached in memory, shared by all functions with `cache = "memory"`.
+static MEMORY: Lazy<Mutex<HashMap<String, Entry>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Request headers which make the request bypass the cache, like a conditional request of the caller.
+const BYPASS_HEADERS: [&str; 5] = ["range", "if-range", "if-match", "if-none-match", "if-modified-since"];
+
+/// Validate a cache option, `memory`, `disk` or `disk:<dir>`.
+pub(crate) fn parse_cache(s: &str) -> Result<String> {
+    HttpCache::parse(s)?;
+    Ok(s.trim().to_string())
+}
+
+/// A private HTTP cache of GET responses, set by the `cache` metadata.
+pub(crate) enum HttpCache {
+    Memory,
+    Disk(PathBuf),
+}
+
+/// A cached response.
+#[derive(Clone)]
+struct Entry {
+    url: String,
+    /// The request headers named by the `vary` header of the response, `None` if the request has no such header.
+    vary: Vec<(String, Option<String>)>,
+    headers: HeaderMap,
+    stored: SystemTime,
+    body: Vec<u8>,
+}
+
+impl HttpCache {
+    pub(crate) fn parse(s: &str) -> Result<HttpCache> {
+        match s.trim() {
+            "memory" => Ok(HttpCache::Memory),
+            "disk" => Ok(HttpCache::Disk(std::env::temp_dir().join("feignhttp-cache"))),
+            s => match s.strip_prefix("disk:") {
+                Some(dir) if !dir.trim().is_empty() => Ok(HttpCache::Disk(PathBuf::from(dir.trim()))),
+                _ => Err(Error::config(format!(
+                    "unsupported cache: {}, expected memory, disk or disk:<dir>",
+                    s
+                ))),
+            },
+        }
+    }
+
+    /// Send a GET request, a fresh cached response is returned without sending it and a stale one is revalidated.
+    pub(crate) async fn send(&self, mut request: RequestWrapper) -> Result<ResponseWrapper> {
+        let Replay { url, headers, .. } = request.snapshot(None);
+        let directives = cache_control(headers.get("cache-control").map(String::as_str));
+        let bypass = BYPASS_HEADERS.iter().any(|key| headers.contains_key(*key))
+            || directives.contains_key("no-store")
+            || Replay::is_replayed(&headers);
+        if bypass {
+            return request.send_uncached(None).await;
+        }
+
+        let now = SystemTime::now();
+        let entry = self.get(&url).filter(|entry| entry.matches(&headers));
+        // `cache-control: no-cache` or `max-age=0` of the request revalidates the cached response.
+        let revalidate = directives.contains_key("no-cache") || directives.get("max-age") == Some(&Some("0"));
+        match &entry {
+            Some(entry) if !revalidate && entry.is_fresh(now) => return entry.response(),
+            Some(entry) => request = request.headers(entry.validators()),
+            None => {}
+        }
+
+        let mut response = request.send_uncached(None).await?;
+        match entry {
+            // The cached response is still valid, its headers are updated by the headers of the 304 response.
+            Some(mut entry) if response.status() == StatusCode::NOT_MODIFIED => {
+                entry.headers.remove(AGE);
+                for (key, value) in response.headers() {
+                    if key != CONTENT_LENGTH && key != CONTENT_ENCODING && key != TRANSFER_ENCODING {
+                        entry.headers.insert(key, value.clone());
+                    }
+                }
+                entry.stored = now;
+                self.put(&entry);
+                entry.response()
+            }
+            _ if is_storable(&response) => {
+                let response_headers = response.headers().clone();
+                let mut body = Vec::new();
+                while let Some(chunk) = response.chunk().await? {
+                    body.extend(chunk);
+                }
+                let entry = Entry {
+                    url,
+                    vary: vary_values(&response_headers, &headers),
+                    headers: response_headers,
+                    stored: now,
+                    body,
+                };
+                self.put(&entry);
+                entry.response()
+            }
+            _ => Ok(response),
+        }
+    }
+
+    fn get(&self, url: &str) -> Option<Entry> {
+        match self {
+            HttpCache::Memory => MEMORY.lock().unwrap().get(url).cloned(),
+            // A file which can't be read is a cache miss.
+            HttpCache::Disk(dir) => fs::read(dir.join(file_name(url)))
+                .ok()
+                .and_then(|data| Entry::decode(&data))
+                .filter(|entry| entry.url == url),
+        }
+    }
+
+    /// Store a response, the request does not fail if it can't be stored.
+    fn put(&self, entry: &Entry) {
+        match self {
+            HttpCache::Memory => {
+                let mut memory = MEMORY.lock().unwrap();
+                if memory.len() >= MAX_MEMORY_ENTRIES && !memory.contains_key(&entry.url) {
+                    let oldest = memory.values().min_by_key(|entry| entry.stored).map(|entry| entry.url.clone());
+                    if let Some(oldest) = oldest {
+                        memory.remove(&oldest);
+                    }
+                }
+                memory.insert(entry.url.clone(), entry.clone());
+            }
+            HttpCache::Disk(dir) => {
+                // The file is replaced at once, a concurrent request never reads a partial file.
+                let path = dir.join(file_name(&entry.url));
+                let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+                let _ = fs::create_dir_all(dir)
+                    .and_then(|_| fs::write(&tmp, entry.encode()))
+                    .and_then(|_| fs::rename(&tmp, &path));
+            }
+        }
+    }
+}
+
+impl Entry {
+    /// Whether the request has the same values of the headers named by `vary` as the cached request.
+    fn matches(&self, headers: &HashMap<String, String>) -> bool {
+        self.vary
+            .iter()
+            .all(|(key, value)| headers.get(key).map(String::as_str) == value.as_deref())
+    }
+
+    fn is_fresh(&self, now: SystemTime) -> bool {
+        let directives = cache_control(self.headers.get(CACHE_CONTROL).and_then(|v| v.to_str().ok()));
+        if directives.contains_key("no-cache") {
+            return false;
+        }
+        let lifetime = match directives.get("max-age") {
+            Some(max_age) => max_age.and_then(|v| v.parse().ok()).map(Duration::from_secs),
+            None => {
+                let date = http_date(&self.headers, DATE).unwrap_or(self.stored);
+                http_date(&self.headers, EXPIRES).map(|expires| expires.duration_since(date).unwrap_or_default())
+            }
+        };
+        let age = self
+            .headers
+            .get(AGE)
+            .and_then(|v| v.to_str().ok()?.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or_default();
+        let elapsed = now.duration_since(self.stored).unwrap_or_default();
+        lifetime.is_some_and(|lifetime| age + elapsed < lifetime)
+    }
+
+    /// The headers of a conditional request to revalidate the response.
+    fn validators(&self) -> HashMap<Cow<'static, str>, String> {
+        let mut validators = HashMap::new();
+        let header = |key| self.headers.get(key).and_then(|v: &HeaderValue| v.to_str().ok());
+        if let Some(etag) = header(ETAG) {
+            validators.insert(Cow::Borrowed("if-none-match"), etag.to_string());
+        }
+        if let Some(last_modified) = header(LAST_MODIFIED) {
+            validators.insert(Cow::Borrowed("if-modified-since"), last_modified.to_string());
+        }
+        validators
+    }
+
+    fn response(&self) -> Result<ResponseWrapper> {
+        // The body is stored decompressed.
+        let mut headers = self.headers.clone();
+        headers.remove(CONTENT_ENCODING);
+        headers.remove(TRANSFER_ENCODING);
+        headers.insert(CONTENT_LENGTH, HeaderValue::from(self.body.len()));
+        ResponseWrapper::from_parts(StatusCode::OK, headers, self.body.clone())
+    }
+
+    /// Encode the entry for the disk cache, lines of the url, the time, the vary values and the headers, an empty
+    /// line and the body.
+    fn encode(&self) -> Vec<u8> {
+        let stored = self.stored.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let mut data = format!("{}\n{}\n", self.url, stored);
+        for (key, value) in &self.vary {
+            match value {
+                Some(value) => data.push_str(&format!("vary {}: {}\n", key, value)),
+                None => data.push_str(&format!("vary {}\n", key)),
+            }
+        }
+        for (key, value) in &self.headers {
+            if let Ok(value) = value.to_str() {
+                data.push_str(&format!("{}: {}\n", key, value));
+            }
+        }
+        data.push('\n');
+        let mut data = data.into_bytes();
+        data.extend_from_slice(&self.body);
+        data
+    }
+
+    fn decode(data: &[u8]) -> Option<Entry> {
+        let end = data.windows(2).position(|w| w == b"\n\n")?;
+        let head = std::str::from_utf8(&data[..end]).ok()?;
+        let mut lines = head.lines();
+        let url = lines.next()?.to_string();
+        let stored = UNIX_EPOCH + Duration::from_secs(lines.next()?.parse().ok()?);
+        let mut entry = Entry {
+            url,
+            vary: Vec::new(),
+            headers: HeaderMap::new(),
+            stored,
+            body: data[end + 2..].to_vec(),
+        };
+        for line in lines {
+            match line.strip_prefix("vary ") {
+                Some(vary) => match vary.split_once(": ") {
+                    Some((key, value)) => entry.vary.push((key.to_string(), Some(value.to_string()))),
+                    None => entry.vary.push((vary.to_string(), None)),
+                },
+                None => {
+                    let (key, value) = line.split_once(": ")?;
+                    let key = HeaderName::from_bytes(key.as_bytes()).ok()?;
+                    entry.headers.append(key, HeaderValue::from_str(value).ok()?);
+                }
+            }
+        }
+        Some(entry)
+    }
+}
+
+/// Whether a response can be stored, it must be fresh for a while or have a validator.
+fn is_storable(response: &ResponseWrapper) -> bool {
+    let headers = response.headers();
+    let directives = cache_control(headers.get(CACHE_CONTROL).and_then(|v| v.to_str().ok()));
+    let vary_all = headers
+        .get_all(VARY)
+        .iter()
+        .any(|v| v.to_str().map_or(true, |v| v.split(',').any(|key| key.trim() == "*")));
+    response.status() == StatusCode::OK
+        && !directives.contains_key("no-store")
+        && !vary_all
+        && (directives.contains_key("max-age")
+            || headers.contains_key(EXPIRES)
+            || headers.contains_key(ETAG)
+            || headers.contains_key(LAST_MODIFIED))
+}
+
+/// The values of the request headers named by the `vary` header of the response.
+fn vary_values(response_headers: &HeaderMap, headers: &HashMap<String, String>) -> Vec<(String, Option<String>)> {
+    response_headers
+        .get_all(VARY)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|key| key.trim().to_lowercase())
+        .filter(|key| !key.is_empty())
+        .map(|key| {
+            let value = headers.get(&key).cloned();
+            (key, value)
+        })
+        .collect()
+}
+
+/// The directives of `cache-control`, like `max-age=60, no-cache`.
+fn cache_control(value: Option<&str>) -> HashMap<String, Option<&str>> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .filter(|directive| !directive.trim().is_empty())
+        .map(|directive| match directive.split_once('=') {
+            Some((key, value)) => (key.trim().to_lowercase(), Some(value.trim().trim_matches('"'))),
+            None => (directive.trim().to_lowercase(), None),
+        })
+        .collect()
+}
+
+fn http_date(headers: &HeaderMap, key: HeaderName) -> Option<SystemTime> {
+    httpdate::parse_http_date(headers.get(key)?.to_str().ok()?).ok()
+}
+
+/// The file name of a cached url, a 64-bit FNV-1a hash which is stable across builds.
+fn file_name(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf29ce484222325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,136 @@
+//! Codecs which serialize request bodies and deserialize responses.
+//!
+//! A codec is selected by `codec = "xml"` in a function or `#[feign]`, JSON is used by default.
+
+use crate::error::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Serialize a value into a request body.
+pub trait Encoder {
+    /// The content type of the body, a `content-type` header takes precedence.
+    fn content_type(&self) -> &'static str;
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>>;
+}
+
+/// Deserialize a value from a response body.
+pub trait Decoder {
+    /// The media types sent by the `accept` header and expected in the response `content-type`.
+    fn accept(&self) -> &'static str;
+
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T>;
+}
+
+/// The JSON codec, it is implemented with the `json` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+#[cfg(feature = "json")]
+impl Encoder for JsonCodec {
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>> {
+        serde_json::to_vec(value).map_err(crate::error::Error::encode)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Decoder for JsonCodec {
+    fn accept(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T> {
+        serde_json::from_slice(body).map_err(crate::error::Error::decode)
+    }
+}
+
+/// The XML codec, the root element is named after the type.
+#[cfg(feature = "xml")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XmlCodec;
+
+#[cfg(feature = "xml")]
+impl Encoder for XmlCodec {
+    fn content_type(&self) -> &'static str {
+        "application/xml"
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>> {
+        quick_xml::se::to_string(value)
+            .map(String::into_bytes)
+            .map_err(crate::error::Error::encode)
+    }
+}
+
+#[cfg(feature = "xml")]
+impl Decoder for XmlCodec {
+    fn accept(&self) -> &'static str {
+        "application/xml, text/xml"
+    }
+
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T> {
+        let body = std::str::from_utf8(body).map_err(crate::error::Error::decode)?;
+        quick_xml::de::from_str(body).map_err(crate::error::Error::decode)
+    }
+}
+
+/// The MessagePack codec, structs are encoded as maps with field names.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgPackCodec;
+
+#[cfg(feature = "msgpack")]
+impl Encoder for MsgPackCodec {
+    fn content_type(&self) -> &'static str {
+        "application/msgpack"
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>> {
+        rmp_serde::to_vec_named(value).map_err(crate::error::Error::encode)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl Decoder for MsgPackCodec {
+    fn accept(&self) -> &'static str {
+        "application/msgpack, application/x-msgpack, application/vnd.msgpack"
+    }
+
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T> {
+        rmp_serde::from_slice(body).map_err(crate::error::Error::decode)
+    }
+}
+
+/// The CBOR codec.
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborCodec;
+
+#[cfg(feature = "cbor")]
+impl Encoder for CborCodec {
+    fn content_type(&self) -> &'static str {
+        "application/cbor"
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>> {
+        let mut body = Vec::new();
+        ciborium::ser::into_writer(value, &mut body)
+            .map_err(|e| crate::error::Error::encode(e.to_string()))?;
+        Ok(body)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl Decoder for CborCodec {
+    fn accept(&self) -> &'static str {
+        "application/cbor"
+    }
+
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T> {
+        ciborium::de::from_reader(body).map_err(|e| crate::error::Error::decode(e.to_string()))
+    }
+}
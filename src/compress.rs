@@ -0,0 +1,64 @@
+use crate::error::{Error, Result};
+#[cfg(any(feature = "gzip", feature = "deflate", feature = "brotli"))]
+use std::io::Write;
+
+/// Content encodings supported by the `compress` metadata.
+const ENCODINGS: [&str; 3] = ["gzip", "deflate", "br"];
+
+/// Check the `compress` metadata and return the content encoding.
+pub(crate) fn parse_encoding(encoding: &str) -> Result<String> {
+    let encoding = encoding.trim().to_lowercase();
+    if !ENCODINGS.contains(&encoding.as_str()) {
+        return Err(Error::config(format!(
+            "unsupported compress encoding: {}, expected one of {}",
+            encoding,
+            ENCODINGS.join(", ")
+        )));
+    }
+    Ok(encoding)
+}
+
+/// Compress a request body with the content encoding.
+#[cfg_attr(
+    not(any(feature = "gzip", feature = "deflate", feature = "brotli")),
+    allow(unused_variables)
+)]
+pub(crate) fn compress(encoding: &str, body: &[u8]) -> Result<Vec<u8>> {
+    match encoding {
+        #[cfg(feature = "gzip")]
+        "gzip" => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(body).map_err(Error::encode)?;
+            encoder.finish().map_err(Error::encode)
+        }
+        #[cfg(feature = "deflate")]
+        "deflate" => {
+            let mut encoder =
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(body).map_err(Error::encode)?;
+            encoder.finish().map_err(Error::encode)
+        }
+        #[cfg(feature = "brotli")]
+        "br" => {
+            let mut out = Vec::new();
+            {
+                let mut encoder = brotli::CompressorWriter::new(&mut out, 4096, 5, 22);
+                encoder.write_all(body).map_err(Error::encode)?;
+            }
+            Ok(out)
+        }
+        _ => Err(Error::config(format!(
+            "compress encoding {} is not enabled, enable the `{}` feature",
+            encoding,
+            feature_name(encoding)
+        ))),
+    }
+}
+
+fn feature_name(encoding: &str) -> &str {
+    match encoding {
+        "br" => "brotli",
+        _ => encoding,
+    }
+}
@@ -0,0 +1,235 @@
+use crate::error::{Error, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Client profiles registered by [`register_profile`] or [`load_config`].
+static PROFILES: Lazy<RwLock<HashMap<String, ClientConfig>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Configuration of a client, loaded from files or environment variables.
+///
+/// A configuration registered as a profile is referenced by name from `#[feign(profile = "name")]` or
+/// `#[get(profile = "name")]`, it supplies defaults that the attribute metadata can override.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+    /// The base url, used when the attribute has no url.
+    pub base_url: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub connect_timeout: Option<Duration>,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub timeout: Option<Duration>,
+    /// Content encoding used to compress the request body, like `gzip`.
+    pub compress: Option<String>,
+    /// Cache of GET responses, `memory`, `disk` or `disk:<dir>`.
+    pub cache: Option<String>,
+    /// Default headers, headers in the attribute or arguments take precedence.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+impl ClientConfig {
+    /// Get the profile registered with the name, overridden by environment variables prefixed with the name.
+    pub fn named(name: &str) -> Result<ClientConfig> {
+        let config = PROFILES.read().unwrap().get(name).cloned();
+        let env_config = ClientConfig::from_env_vars(name)?;
+        match (config, env_config) {
+            (Some(config), Some(env_config)) => Ok(config.merge(env_config)),
+            (Some(config), None) => Ok(config),
+            (None, Some(env_config)) => Ok(env_config),
+            (None, None) => Err(Error::config(format!("profile {} not found", name))),
+        }
+    }
+
+    /// Load a configuration from environment variables.
+    ///
+    /// The variable names are the prefix in upper case, two underscores and the key, like
+    /// `GITHUB__BASE_URL`, `GITHUB__TIMEOUT=5s` or `GITHUB__HEADERS__ACCEPT=application/json`.
+    pub fn from_env(prefix: &str) -> Result<ClientConfig> {
+        Ok(ClientConfig::from_env_vars(prefix)?.unwrap_or_default())
+    }
+
+    /// Parse named configurations from a TOML document, each table is a configuration.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(s: &str) -> Result<HashMap<String, ClientConfig>> {
+        toml::from_str(s).map_err(Error::config)
+    }
+
+    /// Parse named configurations from a YAML document, each mapping is a configuration.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(s: &str) -> Result<HashMap<String, ClientConfig>> {
+        serde_yaml::from_str(s).map_err(Error::config)
+    }
+
+    /// Merge two configurations, the values in `other` take precedence.
+    pub fn merge(mut self, other: ClientConfig) -> ClientConfig {
+        if other.base_url.is_some() {
+            self.base_url = other.base_url;
+        }
+        if other.connect_timeout.is_some() {
+            self.connect_timeout = other.connect_timeout;
+        }
+        if other.timeout.is_some() {
+            self.timeout = other.timeout;
+        }
+        if other.compress.is_some() {
+            self.compress = other.compress;
+        }
+        if other.cache.is_some() {
+            self.cache = other.cache;
+        }
+        self.headers.extend(other.headers);
+        self
+    }
+
+    /// Set a value by key, like `timeout` or `base_url`.
+    pub(crate) fn set(&mut self, key: &str, value: String) -> Result<()> {
+        match key {
+            "base_url" => self.base_url = Some(value),
+            "connect_timeout" => self.connect_timeout = Some(parse_duration(&value)?),
+            "timeout" => self.timeout = Some(parse_duration(&value)?),
+            "compress" => self.compress = Some(crate::compress::parse_encoding(&value)?),
+            "cache" => self.cache = Some(crate::cache::parse_cache(&value)?),
+            _ => return Err(Error::config(format!("unknown config key {}", key))),
+        }
+        Ok(())
+    }
+
+    fn from_env_vars(prefix: &str) -> Result<Option<ClientConfig>> {
+        let prefix = format!("{}__", prefix.to_uppercase().replace('-', "_"));
+        let mut config = ClientConfig::default();
+        let mut found = false;
+        for (key, value) in std::env::vars() {
+            let key = match key.strip_prefix(&prefix) {
+                Some(key) => key,
+                None => continue,
+            };
+            found = true;
+            match key.strip_prefix("HEADERS__") {
+                Some(header) => {
+                    let header = header.to_lowercase().replace('_', "-");
+                    config.headers.insert(header, value);
+                }
+                None => config
+                    .set(&key.to_lowercase(), value)
+                    .map_err(|e| Error::config(format!("invalid {}{}: {}", prefix, key, e)))?,
+            }
+        }
+        Ok(if found { Some(config) } else { None })
+    }
+}
+
+/// Register a profile with the name, a profile with the same name is replaced.
+///
+/// ```
+/// use feignhttp::{register_profile, ClientConfig};
+/// use std::time::Duration;
+///
+/// register_profile("billing", ClientConfig {
+///     base_url: Some("https://billing.example.com".to_string()),
+///     timeout: Some(Duration::from_secs(5)),
+///     ..Default::default()
+/// });
+/// ```
+pub fn register_profile(name: &str, config: ClientConfig) {
+    PROFILES.write().unwrap().insert(name.to_string(), config);
+}
+
+/// Load profiles from a TOML or YAML file, the format is detected by the file extension.
+///
+/// Each top level table is a profile, it can be referenced by `#[feign(profile = "name")]`:
+///
+/// ```toml
+/// [github]
+/// base_url = "https://api.github.com"
+/// timeout = "5s"
+///
+/// [github.headers]
+/// accept = "application/vnd.github.v3+json"
+/// ```
+pub fn load_config<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path).map_err(Error::config)?;
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let configs = parse_configs(extension, &content)?.ok_or_else(|| {
+        Error::config(format!(
+            "unsupported config file: {}, enable the `toml` or `yaml` feature",
+            path.display()
+        ))
+    })?;
+    for (name, config) in configs {
+        if let Some(compress) = &config.compress {
+            crate::compress::parse_encoding(compress)?;
+        }
+        if let Some(cache) = &config.cache {
+            crate::cache::parse_cache(cache)?;
+        }
+        register_profile(&name, config);
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(any(feature = "toml", feature = "yaml")), allow(unused_variables))]
+fn parse_configs(extension: &str, content: &str) -> Result<Option<HashMap<String, ClientConfig>>> {
+    match extension {
+        #[cfg(feature = "toml")]
+        "toml" => ClientConfig::from_toml_str(content).map(Some),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => ClientConfig::from_yaml_str(content).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Parse a human readable duration like `5s`, `300ms`, `1m30s` or `2h`.
+/// A number without unit is in milliseconds.
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    if let Ok(millis) = s.parse::<u64>() {
+        return Ok(Duration::from_millis(millis));
+    }
+    let invalid = || Error::config(format!("invalid duration: {}", s));
+    let mut duration = Duration::ZERO;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+        if digits == 0 {
+            return Err(invalid());
+        }
+        let value = rest[..digits].parse::<u64>().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        duration += match &rest[..unit_len] {
+            "ms" => Duration::from_millis(value),
+            "s" => Duration::from_secs(value),
+            "m" => Duration::from_secs(value * 60),
+            "h" => Duration::from_secs(value * 60 * 60),
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit_len..];
+    }
+    Ok(duration)
+}
+
+fn deserialize_duration<'de, D>(deserializer: D) -> std::result::Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum DurationValue {
+        Millis(u64),
+        Text(String),
+    }
+
+    match Option::<DurationValue>::deserialize(deserializer)? {
+        Some(DurationValue::Millis(millis)) => Ok(Some(Duration::from_millis(millis))),
+        Some(DurationValue::Text(s)) => parse_duration(&s)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
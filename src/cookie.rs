@@ -0,0 +1,309 @@
+use std::io::{self, BufRead, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use url::Url;
+
+/// A cookie store that persists `Set-Cookie` responses and replays them on subsequent requests.
+///
+/// Cloning a `CookieJar` is cheap, all clones share the same cookies.
+#[derive(Clone, Default)]
+pub struct CookieJar {
+    cookies: Arc<Mutex<Vec<Cookie>>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Cookie {
+    name: String,
+    value: String,
+    domain: String,
+    // The cookie has no `Domain` attribute, only the origin host receives it.
+    host_only: bool,
+    path: String,
+    secure: bool,
+    http_only: bool,
+    // Expiry time in seconds since the unix epoch, `None` for a session cookie.
+    expires: Option<u64>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        CookieJar::default()
+    }
+
+    /// Add a cookie in `Set-Cookie` header format, as if it was received from the url.
+    pub fn add_cookie_str(&self, cookie: &str, url: &str) {
+        if let Ok(url) = Url::parse(url) {
+            self.store(std::iter::once(cookie), &url);
+        }
+    }
+
+    /// Get the `Cookie` header value to send to the url.
+    pub fn cookies(&self, url: &str) -> Option<String> {
+        let url = Url::parse(url).ok()?;
+        self.header_value(&url, &[])
+    }
+
+    /// Remove all cookies.
+    pub fn clear(&self) {
+        self.cookies.lock().unwrap().clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let now = now();
+        self.cookies
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|cookie| cookie.is_expired(now))
+    }
+
+    /// Save all unexpired cookies, session cookies included, in the Netscape cookie file format.
+    pub fn save<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let now = now();
+        writeln!(writer, "# Netscape HTTP Cookie File")?;
+        for cookie in self.cookies.lock().unwrap().iter() {
+            if cookie.is_expired(now) {
+                continue;
+            }
+            let prefix = if cookie.http_only { "#HttpOnly_" } else { "" };
+            let domain = if cookie.host_only {
+                cookie.domain.clone()
+            } else {
+                format!(".{}", cookie.domain)
+            };
+            writeln!(
+                writer,
+                "{}{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                prefix,
+                domain,
+                bool_str(!cookie.host_only),
+                cookie.path,
+                bool_str(cookie.secure),
+                cookie.expires.unwrap_or(0),
+                cookie.name,
+                cookie.value,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Restore cookies saved by [`CookieJar::save`].
+    pub fn load<R: BufRead>(reader: R) -> io::Result<CookieJar> {
+        let mut cookies = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let (line, http_only) = match line.strip_prefix("#HttpOnly_") {
+                Some(line) => (line, true),
+                None => (line.as_str(), false),
+            };
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 7 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid cookie line: {}", line),
+                ));
+            }
+            let expires = fields[4].parse::<u64>().map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("invalid cookie expires: {}", e))
+            })?;
+            cookies.push(Cookie {
+                name: fields[5].to_string(),
+                value: fields[6].to_string(),
+                domain: fields[0].trim_start_matches('.').to_lowercase(),
+                host_only: fields[1] != "TRUE",
+                path: fields[2].to_string(),
+                secure: fields[3] == "TRUE",
+                http_only,
+                expires: if expires == 0 { None } else { Some(expires) },
+            });
+        }
+        Ok(CookieJar {
+            cookies: Arc::new(Mutex::new(cookies)),
+        })
+    }
+
+    /// Store the `Set-Cookie` header values received from the url.
+    pub(crate) fn store<'a>(&self, set_cookies: impl Iterator<Item = &'a str>, url: &Url) {
+        let now = now();
+        let mut cookies = self.cookies.lock().unwrap();
+        for set_cookie in set_cookies {
+            let cookie = match Cookie::parse(set_cookie, url, now) {
+                Some(cookie) => cookie,
+                None => continue,
+            };
+            cookies.retain(|c| {
+                !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path)
+            });
+            if !cookie.is_expired(now) {
+                cookies.push(cookie);
+            }
+        }
+    }
+
+    /// Build the `Cookie` header value for the url. Cookies whose name is in `excludes` are skipped.
+    pub(crate) fn header_value(&self, url: &Url, excludes: &[&str]) -> Option<String> {
+        let now = now();
+        let mut cookies = self.cookies.lock().unwrap();
+        cookies.retain(|cookie| !cookie.is_expired(now));
+        let mut matched: Vec<&Cookie> = cookies
+            .iter()
+            .filter(|cookie| cookie.matches(url) && !excludes.contains(&cookie.name.as_str()))
+            .collect();
+        if matched.is_empty() {
+            return None;
+        }
+        // Cookies with longer paths are listed first.
+        matched.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        let pairs: Vec<String> = matched
+            .iter()
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        Some(pairs.join("; "))
+    }
+}
+
+impl Cookie {
+    /// Parse a `Set-Cookie` header value according to RFC 6265.
+    fn parse(set_cookie: &str, url: &Url, now: u64) -> Option<Cookie> {
+        let host = url.host_str()?.to_lowercase();
+        let mut parts = set_cookie.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url),
+            secure: false,
+            http_only: false,
+            expires: None,
+        };
+        let mut max_age = None;
+        for attr in parts {
+            let (key, value) = match attr.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (attr.trim(), ""),
+            };
+            match key.to_lowercase().as_str() {
+                "expires" => {
+                    if let Ok(time) = httpdate::parse_http_date(value) {
+                        cookie.expires = Some(unix_secs(time));
+                    }
+                }
+                "max-age" => {
+                    if let Ok(secs) = value.parse::<i64>() {
+                        max_age = Some(secs);
+                    }
+                }
+                "domain" => {
+                    let domain = value.trim_start_matches('.').to_lowercase();
+                    if !domain.is_empty() {
+                        // Reject cookies for a domain the host does not belong to.
+                        if !domain_match(&host, &domain) {
+                            return None;
+                        }
+                        cookie.domain = domain;
+                        cookie.host_only = false;
+                    }
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                _ => {}
+            }
+        }
+        // `Max-Age` has precedence over `Expires`.
+        if let Some(secs) = max_age {
+            cookie.expires = Some(if secs <= 0 { 0 } else { now + secs as u64 });
+        }
+        Some(cookie)
+    }
+
+    fn is_expired(&self, now: u64) -> bool {
+        matches!(self.expires, Some(expires) if expires <= now)
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let host = match url.host_str() {
+            Some(host) => host.to_lowercase(),
+            None => return false,
+        };
+        let domain_matched = if self.host_only {
+            host == self.domain
+        } else {
+            domain_match(&host, &self.domain)
+        };
+        domain_matched && path_match(url.path(), &self.path) && (!self.secure || url.scheme() == "https")
+    }
+}
+
+fn domain_match(host: &str, domain: &str) -> bool {
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+fn path_match(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// The default cookie path is the directory of the request path.
+fn default_path(url: &Url) -> String {
+    let path = url.path();
+    match path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(i) => path[..i].to_string(),
+    }
+}
+
+fn bool_str(b: bool) -> &'static str {
+    if b {
+        "TRUE"
+    } else {
+        "FALSE"
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn now() -> u64 {
+    unix_secs(SystemTime::now())
+}
+
+/// Build the `Cookie` header value from an existing header value, the explicit cookies and the jar.
+/// Explicit cookies override cookies with the same name in the jar.
+pub(crate) fn cookie_header(
+    existing: Option<&String>,
+    cookies: &[(String, String)],
+    cookie_jar: Option<&CookieJar>,
+    url: &Url,
+) -> Option<String> {
+    let mut pairs = Vec::new();
+    if let Some(existing) = existing {
+        pairs.push(existing.clone());
+    }
+    if let Some(cookie_jar) = cookie_jar {
+        let names: Vec<&str> = cookies.iter().map(|(name, _)| name.as_str()).collect();
+        if let Some(value) = cookie_jar.header_value(url, &names) {
+            pairs.push(value);
+        }
+    }
+    for (name, value) in cookies {
+        pairs.push(format!("{}={}", name, value));
+    }
+    if pairs.is_empty() {
+        None
+    } else {
+        Some(pairs.join("; "))
+    }
+}
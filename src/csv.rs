@@ -0,0 +1,241 @@
+use crate::error::{Error, Result};
+use crate::http::HttpResponse;
+use crate::stream::Chunks;
+use crate::{FromResponse, ResponseWrapper};
+use async_trait::async_trait;
+use csv_core::ReadRecordResult;
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+/// Configuration of CSV decoding, set by `csv_delimiter`, `csv_quote` and `csv_headers` metadata.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsvConfig {
+    pub delimiter: u8,
+    pub quote: u8,
+    /// Whether the first row is a header row, which names the fields of the rows.
+    pub has_headers: bool,
+}
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        CsvConfig {
+            delimiter: b',',
+            quote: b'"',
+            has_headers: true,
+        }
+    }
+}
+
+impl CsvConfig {
+    fn core_reader(&self) -> csv_core::Reader {
+        csv_core::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .build()
+    }
+}
+
+/// A type which can be received from a CSV response with a configuration.
+#[async_trait]
+pub trait FromCsv: Sized {
+    async fn from_csv(response: ResponseWrapper, config: CsvConfig) -> Result<Self>;
+}
+
+/// The rows of a CSV response, deserialized by serde, like `feignhttp::Result<Csv<Row>>`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Csv<T>(pub Vec<T>);
+
+impl<T> Csv<T> {
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for Csv<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<T: DeserializeOwned> FromCsv for Csv<T> {
+    async fn from_csv(response: ResponseWrapper, config: CsvConfig) -> Result<Self> {
+        let body = response.vec().await?;
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(config.delimiter)
+            .quote(config.quote)
+            .has_headers(config.has_headers)
+            .from_reader(body.as_slice());
+        let mut rows = Vec::new();
+        for (i, row) in reader.deserialize().enumerate() {
+            let row = row.map_err(|e| Error::decode(format!("invalid csv at row {}: {}", i + 1, e)))?;
+            rows.push(row);
+        }
+        Ok(Csv(rows))
+    }
+}
+
+#[async_trait]
+impl<T: DeserializeOwned> FromResponse for Csv<T> {
+    fn accept() -> Option<&'static str> {
+        Some("text/csv")
+    }
+
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        Csv::from_csv(response, CsvConfig::default()).await
+    }
+}
+
+/// A stream of the rows of a CSV response, like `feignhttp::Result<CsvStream<Row>>`.
+///
+/// Rows are deserialized as they arrive. A row which fails to decode is an error with its row number,
+/// and the stream goes on with the next row.
+pub struct CsvStream<T> {
+    chunks: Chunks,
+    reader: csv_core::Reader,
+    has_headers: bool,
+    headers: Option<::csv::StringRecord>,
+    input: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    done: bool,
+    // The fields of the record being read, and their end offsets.
+    output: Vec<u8>,
+    out_len: usize,
+    ends: Vec<usize>,
+    ends_len: usize,
+    row: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> CsvStream<T> {
+    pub fn new(response: ResponseWrapper, config: CsvConfig) -> Self {
+        CsvStream {
+            chunks: Chunks::new(response),
+            reader: config.core_reader(),
+            has_headers: config.has_headers,
+            headers: None,
+            input: Vec::new(),
+            pos: 0,
+            eof: false,
+            done: false,
+            output: vec![0; 1024],
+            out_len: 0,
+            ends: vec![0; 16],
+            ends_len: 0,
+            row: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Read a record from the received bytes, `None` if more bytes are needed or the stream ends.
+    fn read_record(&mut self) -> Result<Option<::csv::StringRecord>> {
+        loop {
+            // An empty input means the end of the body to the reader.
+            if !self.eof && self.pos == self.input.len() {
+                return Ok(None);
+            }
+            let input = if self.eof { &[][..] } else { &self.input[self.pos..] };
+            let (result, nin, nout, nend) = self.reader.read_record(
+                input,
+                &mut self.output[self.out_len..],
+                &mut self.ends[self.ends_len..],
+            );
+            self.pos += nin;
+            self.out_len += nout;
+            self.ends_len += nend;
+            match result {
+                ReadRecordResult::InputEmpty => return Ok(None),
+                ReadRecordResult::OutputFull => self.output.resize(self.output.len() * 2, 0),
+                ReadRecordResult::OutputEndsFull => self.ends.resize(self.ends.len() * 2, 0),
+                ReadRecordResult::Record => {
+                    let mut record = ::csv::StringRecord::with_capacity(self.out_len, self.ends_len);
+                    let mut start = 0;
+                    for &end in &self.ends[..self.ends_len] {
+                        let field = std::str::from_utf8(&self.output[start..end]).map_err(|e| {
+                            Error::decode(format!("invalid csv at row {}: {}", self.row + 1, e))
+                        })?;
+                        record.push_field(field);
+                        start = end;
+                    }
+                    self.out_len = 0;
+                    self.ends_len = 0;
+                    return Ok(Some(record));
+                }
+                ReadRecordResult::End => {
+                    self.done = true;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Stream for CsvStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+        let this = self.get_mut();
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+            match this.read_record() {
+                Ok(Some(record)) if this.has_headers && this.headers.is_none() => {
+                    this.headers = Some(record);
+                    continue;
+                }
+                Ok(Some(record)) => {
+                    this.row += 1;
+                    let row = record.deserialize(this.headers.as_ref()).map_err(|e| {
+                        Error::decode(format!("invalid csv at row {}: {}", this.row, e))
+                    });
+                    return Poll::Ready(Some(row));
+                }
+                Ok(None) if this.done => return Poll::Ready(None),
+                Ok(None) => {}
+                Err(e) => {
+                    this.row += 1;
+                    this.out_len = 0;
+                    this.ends_len = 0;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+            match ready!(this.chunks.poll_chunk(cx)) {
+                Some(Ok(chunk)) => {
+                    this.input = chunk;
+                    this.pos = 0;
+                }
+                Some(Err(e)) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => this.eof = true,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<T> FromCsv for CsvStream<T> {
+    async fn from_csv(response: ResponseWrapper, config: CsvConfig) -> Result<Self> {
+        Ok(CsvStream::new(response, config))
+    }
+}
+
+#[async_trait]
+impl<T> FromResponse for CsvStream<T> {
+    fn accept() -> Option<&'static str> {
+        Some("text/csv")
+    }
+
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        Ok(CsvStream::new(response, CsvConfig::default()))
+    }
+}
@@ -0,0 +1,201 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::http::HttpResponse;
+use crate::{RequestWrapper, ResponseWrapper};
+use http::header::{CONTENT_DISPOSITION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE, ETAG};
+use http::{HeaderMap, StatusCode};
+use percent_encoding::percent_decode_str;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// A file downloaded by a function with a `#[download]` argument, like `feignhttp::Result<Download>`.
+///
+/// The response is written to a `.part` file next to the file, which is renamed to the file when the
+/// download is complete. An interrupted download is resumed by a `Range` request if the response had an ETag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Download {
+    /// The path of the downloaded file.
+    pub path: PathBuf,
+    /// The length of the file in bytes.
+    pub len: u64,
+    /// Whether the partial file of an interrupted download was resumed.
+    pub resumed: bool,
+}
+
+impl Download {
+    /// Send the request and download the response to a file, or to a directory with the file name of the
+    /// `content-disposition` header.
+    #[doc(hidden)]
+    pub async fn start<P: AsRef<Path>>(request: RequestWrapper, dest: P) -> Result<Download> {
+        let dest = dest.as_ref();
+        let replay = request.snapshot(None);
+
+        // The file name is unknown before the response, so a download to a directory is not resumed.
+        if dest.is_dir() {
+            let response = replay.send(Vec::new()).await?;
+            let partial = Partial::new(dest.join(file_name(response.headers(), &replay.url)));
+            return partial.receive(response, None).await;
+        }
+
+        let partial = Partial::new(dest.to_path_buf());
+        let resume = partial.resumable();
+        let mut headers = Vec::new();
+        if let Some((len, etag)) = &resume {
+            headers.push(("range".to_string(), format!("bytes={}-", len)));
+            headers.push(("if-range".to_string(), etag.clone()));
+        }
+        let response = match replay.send(headers).await {
+            // The partial file is not a part of the file any more, download the whole file.
+            Err(e) if resume.is_some() && is_range_not_satisfiable(&e) => {
+                partial.remove();
+                return partial.receive(replay.send(Vec::new()).await?, None).await;
+            }
+            result => result?,
+        };
+        partial.receive(response, resume.map(|(len, _)| len)).await
+    }
+}
+
+fn is_range_not_satisfiable(e: &Error) -> bool {
+    matches!(e.error_kind(), ErrorKind::Status(StatusCode::RANGE_NOT_SATISFIABLE))
+}
+
+/// The partial file of a download and the file of its ETag.
+struct Partial {
+    path: PathBuf,
+    part: PathBuf,
+    etag: PathBuf,
+}
+
+impl Partial {
+    fn new(path: PathBuf) -> Self {
+        let with_extension = |extension: &str| {
+            let mut name = OsString::from(path.as_os_str());
+            name.push(extension);
+            PathBuf::from(name)
+        };
+        Partial {
+            part: with_extension(".part"),
+            etag: with_extension(".part.etag"),
+            path,
+        }
+    }
+
+    /// The length of the partial file and the ETag of the file, if the download can be resumed.
+    fn resumable(&self) -> Option<(u64, String)> {
+        let len = fs::metadata(&self.part).ok()?.len();
+        let etag = fs::read_to_string(&self.etag).ok()?;
+        (len > 0 && !etag.is_empty()).then_some((len, etag))
+    }
+
+    fn remove(&self) {
+        let _ = fs::remove_file(&self.part);
+        let _ = fs::remove_file(&self.etag);
+    }
+
+    /// Write the response to the partial file, appending to it for a partial response of a resumed download.
+    async fn receive(&self, mut response: ResponseWrapper, resume: Option<u64>) -> Result<Download> {
+        let headers = response.headers();
+        let (mut file, offset) = match resume {
+            Some(len) if response.status() == StatusCode::PARTIAL_CONTENT => {
+                let start = content_range(headers).map(|(start, _)| start);
+                if start != Some(len) {
+                    return Err(Error::decode(format!(
+                        "unexpected content range, expected the bytes from {}",
+                        len
+                    )));
+                }
+                (OpenOptions::new().append(true).open(&self.part).map_err(Error::decode)?, len)
+            }
+            // The server sends the whole file if the ETag is changed.
+            _ => (File::create(&self.part).map_err(Error::decode)?, 0),
+        };
+
+        // A strong ETag is kept to resume the download if it is interrupted.
+        match headers.get(ETAG).and_then(|v| v.to_str().ok()).filter(|etag| !etag.starts_with("W/")) {
+            Some(etag) => fs::write(&self.etag, etag).map_err(Error::decode)?,
+            None => {
+                let _ = fs::remove_file(&self.etag);
+            }
+        }
+
+        // The length is unknown if the body is decompressed.
+        let expected = match content_range(headers) {
+            Some((_, total)) if offset > 0 => total,
+            _ if headers.contains_key(CONTENT_ENCODING) => None,
+            _ => headers
+                .get(CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok()?.parse::<u64>().ok())
+                .map(|len| offset + len),
+        };
+
+        let mut len = offset;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).map_err(Error::decode)?;
+            len += chunk.len() as u64;
+        }
+        file.sync_all().map_err(Error::decode)?;
+        drop(file);
+        if let Some(expected) = expected.filter(|expected| *expected != len) {
+            return Err(Error::decode(format!(
+                "incomplete download, received {} of {} bytes",
+                len, expected
+            )));
+        }
+
+        fs::rename(&self.part, &self.path).map_err(Error::decode)?;
+        let _ = fs::remove_file(&self.etag);
+        Ok(Download {
+            path: self.path.clone(),
+            len,
+            resumed: offset > 0,
+        })
+    }
+}
+
+/// The start and the total length of `content-range: bytes 100-199/200`.
+fn content_range(headers: &HeaderMap) -> Option<(u64, Option<u64>)> {
+    let value = headers.get(CONTENT_RANGE)?.to_str().ok()?;
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let start = range.split_once('-')?.0.trim().parse().ok()?;
+    Some((start, total.trim().parse().ok()))
+}
+
+/// The file name of the `content-disposition` header, or the last segment of the url.
+fn file_name(headers: &HeaderMap, url: &str) -> String {
+    let disposition = headers.get(CONTENT_DISPOSITION).and_then(|v| v.to_str().ok()).unwrap_or_default();
+    let mut name = None;
+    for param in disposition.split(';').skip(1) {
+        let (key, value) = match param.split_once('=') {
+            Some((key, value)) => (key.trim().to_lowercase(), value.trim()),
+            None => continue,
+        };
+        match key.as_str() {
+            // An extended value like `UTF-8''r%C3%A9sum%C3%A9.pdf` takes precedence.
+            "filename*" => {
+                if let Some((_, encoded)) = value.split_once("''") {
+                    if let Ok(decoded) = percent_decode_str(encoded).decode_utf8() {
+                        name = Some(decoded.into_owned());
+                        break;
+                    }
+                }
+            }
+            "filename" if name.is_none() => name = Some(value.trim_matches('"').to_string()),
+            _ => {}
+        }
+    }
+    let from_url = || {
+        let url = Url::parse(url).ok()?;
+        let segment = url.path_segments()?.next_back()?.to_string();
+        percent_decode_str(&segment).decode_utf8().ok().map(|s| s.into_owned())
+    };
+    // Only the last component is used, a name can't leave the directory.
+    [name, from_url()]
+        .into_iter()
+        .flatten()
+        .filter_map(|name| name.rsplit(['/', '\\']).next().map(str::to_string))
+        .find(|name| !name.is_empty() && name != "." && name != "..")
+        .unwrap_or_else(|| "download".to_string())
+}
@@ -1,9 +1,14 @@
 use crate::{
+    cache::parse_cache,
+    compress::parse_encoding,
+    config::{parse_duration, ClientConfig},
+    cookie::CookieJar,
     error::{Error, Result},
-    RequestWrapper,
+    progress::{Progress, ProgressFn},
+    RequestWrapper, ResponseWrapper,
 };
 use async_trait::async_trait;
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
 
 /// An HTTP client to create RequestBuilder.
 pub struct HttpClient;
@@ -20,9 +25,18 @@ pub struct RequestBuilder<'a> {
     method: &'a str,
     headers: Option<HashMap<Cow<'a, str>, String>>,
     query: Option<Vec<(&'a str, String)>>,
+    cookies: Option<Vec<(&'a str, String)>>,
+    cookie_jar: Option<CookieJar>,
+    progress: Option<ProgressFn>,
     config: Option<HttpConfig>,
 }
 
+impl Default for RequestBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> RequestBuilder<'a> {
     pub fn new() -> Self {
         Self {
@@ -30,6 +44,9 @@ impl<'a> RequestBuilder<'a> {
             method: "",
             headers: None,
             query: None,
+            cookies: None,
+            cookie_jar: None,
+            progress: None,
             config: None,
         }
     }
@@ -58,7 +75,33 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    pub fn cookies(mut self, cookies: Vec<(&'a str, String)>) -> Self {
+        self.cookies = Some(cookies);
+        self
+    }
+
+    /// Attach a cookie jar, cookies in the jar are sent and `Set-Cookie` responses are stored.
+    pub fn cookie_jar(mut self, cookie_jar: CookieJar) -> Self {
+        self.cookie_jar = Some(cookie_jar);
+        self
+    }
+
+    /// Report the bytes sent and received to a callback as the bodies are transferred.
+    pub fn progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
     pub fn build(self) -> Result<RequestWrapper> {
+        // Reject CR and LF, which can inject headers or split the request.
+        for (key, value) in self.headers.iter().flatten() {
+            if [key.as_ref(), value.as_str()].iter().any(|s| s.contains(['\r', '\n'])) {
+                return Err(Error::build(format!("invalid header {}: contains CR or LF", key)));
+            }
+        }
         let mut request = match self.config {
             Some(config) => RequestWrapper::build_with_config(self.url, self.method, config)?,
             None => RequestWrapper::build_default(self.url, self.method)?,
@@ -69,30 +112,111 @@ impl<'a> RequestBuilder<'a> {
         if let Some(query_vec) = self.query {
             request = request.query(query_vec);
         }
+        if let Some(cookie_vec) = self.cookies {
+            request = request.cookies(cookie_vec);
+        }
+        if let Some(cookie_jar) = self.cookie_jar {
+            request = request.cookie_jar(cookie_jar);
+        }
+        if let Some(progress) = self.progress {
+            request = request.progress(progress);
+        }
         Ok(request)
     }
 }
 
 /// Configuration of an HTTP request.
+#[derive(Clone, Default)]
 pub struct HttpConfig {
     pub connect_timeout: Option<u64>,
     pub timeout: Option<u64>,
+    /// Content encoding used to compress the request body, like `gzip`.
+    pub compress: Option<String>,
+    /// Cache of GET responses, `memory`, `disk` or `disk:<dir>`.
+    pub cache: Option<String>,
 }
 
 impl HttpConfig {
     pub fn from_map(config_map: HashMap<&str, String>) -> Result<Self> {
-        let mut config = HttpConfig {
-            connect_timeout: None,
-            timeout: None,
-        };
+        let mut config = HttpConfig::default();
         if let Some(connect_timeout) = config_map.get("connect_timeout") {
-            config.connect_timeout = Some(connect_timeout.parse::<u64>().map_err(Error::config)?);
+            config.connect_timeout = Some(parse_duration(connect_timeout)?.as_millis() as u64);
         }
         if let Some(timeout) = config_map.get("timeout") {
-            config.timeout = Some(timeout.parse::<u64>().map_err(Error::config)?);
+            config.timeout = Some(parse_duration(timeout)?.as_millis() as u64);
+        }
+        if let Some(compress) = config_map.get("compress") {
+            config.compress = Some(parse_encoding(compress)?);
+        }
+        if let Some(cache) = config_map.get("cache") {
+            config.cache = Some(parse_cache(cache)?);
         }
         Ok(config)
     }
+
+    /// Use the values of a client configuration for the options not set.
+    pub fn with_client_config(mut self, client_config: &ClientConfig) -> Result<Self> {
+        if self.connect_timeout.is_none() {
+            self.connect_timeout = client_config.connect_timeout.map(|d| d.as_millis() as u64);
+        }
+        if self.timeout.is_none() {
+            self.timeout = client_config.timeout.map(|d| d.as_millis() as u64);
+        }
+        if self.compress.is_none() {
+            if let Some(compress) = &client_config.compress {
+                self.compress = Some(parse_encoding(compress)?);
+            }
+        }
+        if self.cache.is_none() {
+            self.cache = client_config.cache.clone();
+        }
+        Ok(self)
+    }
+}
+
+/// A sent request which can be sent again, like reconnecting an event stream.
+#[derive(Clone)]
+pub(crate) struct Replay {
+    pub(crate) url: String,
+    pub(crate) method: String,
+    pub(crate) config: HttpConfig,
+    pub(crate) headers: HashMap<String, String>,
+    pub(crate) cookies: Vec<(String, String)>,
+    pub(crate) cookie_jar: Option<CookieJar>,
+    pub(crate) progress: Option<ProgressFn>,
+    pub(crate) body: Option<Vec<u8>>,
+}
+
+impl Replay {
+    /// Only event streams are kept, the accept header is set by the return type.
+    pub(crate) fn is_replayed(headers: &HashMap<String, String>) -> bool {
+        headers
+            .get("accept")
+            .is_some_and(|accept| accept.contains("text/event-stream"))
+    }
+
+    /// Send the request again with more headers, like `last-event-id`.
+    pub(crate) async fn send(&self, headers: Vec<(String, String)>) -> Result<ResponseWrapper> {
+        let mut request = RequestWrapper::build_with_config(&self.url, &self.method, self.config.clone())?;
+        let mut header_map: HashMap<Cow<str>, String> = self
+            .headers
+            .iter()
+            .map(|(k, v)| (Cow::Borrowed(k.as_str()), v.clone()))
+            .collect();
+        for (k, v) in headers {
+            header_map.insert(Cow::Owned(k), v);
+        }
+        request = request
+            .headers(header_map)
+            .cookies(self.cookies.iter().map(|(k, v)| (k.as_str(), v.clone())).collect());
+        if let Some(cookie_jar) = &self.cookie_jar {
+            request = request.cookie_jar(cookie_jar.clone());
+        }
+        if let Some(progress) = &self.progress {
+            request = request.progress(progress.clone());
+        }
+        request.send_body(self.body.clone()).await
+    }
 }
 
 /// A trait of HTTP request.
@@ -100,6 +224,10 @@ pub trait HttpRequest {
     fn headers(self, headers: HashMap<Cow<str>, String>) -> Self;
 
     fn query(self, query: Vec<(&str, String)>) -> Self;
+
+    fn cookies(self, cookies: Vec<(&str, String)>) -> Self;
+
+    fn cookie_jar(self, cookie_jar: CookieJar) -> Self;
 }
 
 /// A trait of HTTP response.
@@ -107,9 +235,14 @@ pub trait HttpRequest {
 pub trait HttpResponse {
     fn status(&self) -> http::StatusCode;
 
+    fn headers(&self) -> &http::HeaderMap;
+
     async fn none(self) -> Result<()>;
 
     async fn text(self) -> Result<String>;
 
     async fn vec(self) -> Result<Vec<u8>>;
+
+    /// The next chunk of the body as it arrives, `None` at the end of the body.
+    async fn chunk(&mut self) -> Result<Option<Vec<u8>>>;
 }
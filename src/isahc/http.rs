@@ -1,26 +1,51 @@
 #[cfg(feature = "log")]
 use super::log::{print_request_log, print_response_log};
 use crate::{
-    error::Error, error::ErrorKind, error::Result, http::HttpConfig, http::HttpRequest,
+    body::IntoBody,
+    cache::HttpCache,
+    compress::compress,
+    cookie::{cookie_header, CookieJar},
+    error::Error, error::ErrorKind, error::Result, http::HttpConfig, http::HttpRequest, http::Replay,
     http::HttpResponse, map,
+    progress::{Counted, Direction, ProgressFn, Tracker},
 };
 use async_trait::async_trait;
-use http::{request::Builder, Request, Response, StatusCode};
+use futures_io::AsyncRead;
+use http::{header::SET_COOKIE, request::Builder, Request, Response, StatusCode};
 use isahc::{config::RedirectPolicy, prelude::*, AsyncBody};
 use std::collections::HashMap;
+use std::pin::Pin;
 use std::time::Duration;
 use url::Url;
 
+// Let curl negotiate `Accept-Encoding` and decode responses when any compression feature is enabled.
+const DECOMPRESSION: bool = cfg!(any(
+    feature = "gzip",
+    feature = "deflate",
+    feature = "brotli",
+    feature = "zstd"
+));
+
+/// The maximum size of a chunk read from a response body.
+const CHUNK_SIZE: usize = 8 * 1024;
+
 /// A wrapper of HTTP request.
 pub struct RequestWrapper {
     url: Url,
     headers: HashMap<String, String>,
+    cookies: Vec<(String, String)>,
+    cookie_jar: Option<CookieJar>,
+    compress: Option<String>,
+    method: String,
+    config: HttpConfig,
+    progress: Option<ProgressFn>,
     request: Builder,
 }
 
 /// A wrapper of HTTP response.
 pub struct ResponseWrapper {
     response: Response<AsyncBody>,
+    replay: Option<Replay>,
 }
 
 impl HttpRequest for RequestWrapper {
@@ -32,19 +57,15 @@ impl HttpRequest for RequestWrapper {
     }
 
     fn query(mut self, query: Vec<(&str, String)>) -> Self {
-        if query.len() == 0 {
+        if query.is_empty() {
             return self;
         }
         let request = self.request;
         let uri_ref = request.uri_ref().unwrap();
         let mut url = uri_ref.to_string();
         match uri_ref.query() {
-            Some(_) => {
-                url.push_str("&");
-            }
-            None => {
-                url.push_str("?");
-            }
+            Some(_) => url.push('&'),
+            None => url.push('?'),
         }
         let query = serde_urlencoded::to_string(query).unwrap();
         url.push_str(&query);
@@ -52,6 +73,18 @@ impl HttpRequest for RequestWrapper {
         self.request = request.uri(url);
         self
     }
+
+    fn cookies(mut self, cookies: Vec<(&str, String)>) -> Self {
+        for (k, v) in cookies {
+            self.cookies.push((k.to_string(), v));
+        }
+        self
+    }
+
+    fn cookie_jar(mut self, cookie_jar: CookieJar) -> Self {
+        self.cookie_jar = Some(cookie_jar);
+        self
+    }
 }
 
 impl RequestWrapper {
@@ -60,11 +93,18 @@ impl RequestWrapper {
         let request = Request::builder()
             .uri(url.to_string())
             .method(method.to_uppercase().as_str())
-            .redirect_policy(RedirectPolicy::Limit(10));
+            .redirect_policy(RedirectPolicy::Limit(10))
+            .automatic_decompression(DECOMPRESSION);
         Ok(RequestWrapper {
             url,
             headers: map!(
                 "user-agent".to_string() => "Feign HTTP".to_string()),
+            cookies: Vec::new(),
+            cookie_jar: None,
+            compress: None,
+            method: method.to_string(),
+            config: HttpConfig::default(),
+            progress: None,
             request,
         })
     }
@@ -85,16 +125,31 @@ impl RequestWrapper {
         request = request
             .uri(url.to_string())
             .method(method.to_uppercase().as_str())
-            .redirect_policy(RedirectPolicy::Limit(10));
+            .redirect_policy(RedirectPolicy::Limit(10))
+            .automatic_decompression(DECOMPRESSION);
         Ok(RequestWrapper {
             url,
             headers: map!(
                 "user-agent".to_string() => "Feign HTTP".to_string()),
+            cookies: Vec::new(),
+            cookie_jar: None,
+            compress: config.compress.clone(),
+            method: method.to_string(),
+            config,
+            progress: None,
             request,
         })
     }
 
     fn set_header(mut self) -> Self {
+        if let Some(cookie) = cookie_header(
+            self.headers.get("cookie"),
+            &self.cookies,
+            self.cookie_jar.as_ref(),
+            &self.url,
+        ) {
+            self.headers.insert("cookie".to_string(), cookie);
+        }
         let mut request = self.request;
         for (k, v) in &self.headers {
             request = request.header(k.as_str(), v);
@@ -104,16 +159,72 @@ impl RequestWrapper {
     }
 
     fn set_header_if_absent(&mut self, k: &str, v: String) {
-        if let None = self.headers.get(k) {
+        if !self.headers.contains_key(k) {
             self.headers.insert(k.to_string(), v);
         }
     }
 
-    async fn send_body(self, body: Option<Vec<u8>>) -> Result<ResponseWrapper> {
+    fn replay(&self, body: &Option<Vec<u8>>) -> Option<Replay> {
+        if !Replay::is_replayed(&self.headers) {
+            return None;
+        }
+        Some(self.snapshot(body.clone()))
+    }
+
+    /// A copy of the request which can be sent again.
+    pub(crate) fn snapshot(&self, body: Option<Vec<u8>>) -> Replay {
+        Replay {
+            url: self.url.to_string(),
+            method: self.method.clone(),
+            config: self.config.clone(),
+            headers: self.headers.clone(),
+            cookies: self.cookies.clone(),
+            cookie_jar: self.cookie_jar.clone(),
+            progress: self.progress.clone(),
+            body,
+        }
+    }
+
+    /// Report the progress of the request and the response to a callback.
+    pub(crate) fn progress(mut self, progress: ProgressFn) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    pub(crate) async fn send_body(self, body: Option<Vec<u8>>) -> Result<ResponseWrapper> {
+        // A GET request without a body may be served by the cache.
+        if let (Some(cache), None) = (&self.config.cache, &body) {
+            if self.method.eq_ignore_ascii_case("get") {
+                return HttpCache::parse(cache)?.send(self).await;
+            }
+        }
+        self.send_uncached(body).await
+    }
+
+    /// Send the request without the cache.
+    pub(crate) async fn send_uncached(mut self, mut body: Option<Vec<u8>>) -> Result<ResponseWrapper> {
+        let replay = self.replay(&body);
+        if let (Some(encoding), Some(data)) = (&self.compress, &body) {
+            body = Some(compress(encoding, data)?);
+            self.headers
+                .insert("content-encoding".to_string(), encoding.clone());
+        }
+
         let url = self.url.clone();
+        let cookie_jar = self.cookie_jar.clone();
+        let tracker = self.progress.clone().map(Tracker::new);
         let mut async_body = AsyncBody::from(());
         if let Some(body) = body.clone() {
+            let len = body.len() as u64;
             async_body = AsyncBody::from(body);
+            // The body is read by curl as it is sent.
+            if let Some(tracker) = &tracker {
+                tracker.send_total(len);
+                async_body = AsyncBody::from_reader_sized(
+                    Counted::new(async_body, tracker.clone(), Direction::Send),
+                    len,
+                );
+            }
         }
         let request = self
             .set_header()
@@ -129,6 +240,11 @@ impl RequestWrapper {
                 #[cfg(feature = "log")]
                 print_response_log(&response);
 
+                if let Some(cookie_jar) = cookie_jar {
+                    let set_cookies = response.headers().get_all(SET_COOKIE);
+                    cookie_jar.store(set_cookies.iter().flat_map(|v| v.to_str()), &url);
+                }
+
                 let status = response.status();
 
                 // Client or server error.
@@ -136,7 +252,11 @@ impl RequestWrapper {
                     return Err(Error::status(url, status));
                 }
 
-                Ok(ResponseWrapper { response })
+                let response = match tracker {
+                    Some(tracker) => counted_response(response, tracker),
+                    None => response,
+                };
+                Ok(ResponseWrapper { response, replay })
             }
             Err(e) => Err(Error::new(ErrorKind::Request, Some(e)).with_url(url)),
         };
@@ -177,6 +297,24 @@ impl RequestWrapper {
         self.set_header_if_absent("content-type", "application/octet-stream".to_string());
         self.send_body(Some(vec)).await
     }
+
+    pub async fn send_into_body<B: IntoBody>(mut self, body: B) -> Result<ResponseWrapper> {
+        self.set_header_if_absent("content-type", body.content_type().to_string());
+        self.send_body(Some(body.into_body()?)).await
+    }
+}
+
+/// A response whose body reports the progress as it is read.
+fn counted_response(response: Response<AsyncBody>, tracker: Tracker) -> Response<AsyncBody> {
+    tracker.receive_total(response.headers());
+    response.map(|body| {
+        let len = body.len();
+        let body = Counted::new(body, tracker, Direction::Receive);
+        match len {
+            Some(len) => AsyncBody::from_reader_sized(body, len),
+            None => AsyncBody::from_reader(body),
+        }
+    })
 }
 
 #[async_trait]
@@ -185,6 +323,10 @@ impl HttpResponse for ResponseWrapper {
         self.response.status()
     }
 
+    fn headers(&self) -> &http::HeaderMap {
+        self.response.headers()
+    }
+
     async fn none(self) -> Result<()> {
         Ok(())
     }
@@ -196,9 +338,40 @@ impl HttpResponse for ResponseWrapper {
     async fn vec(mut self) -> Result<Vec<u8>> {
         self.response.bytes().await.map_err(Error::decode)
     }
+
+    async fn chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut buf = vec![0; CHUNK_SIZE];
+        let body = self.response.body_mut();
+        let n = std::future::poll_fn(|cx| Pin::new(&mut *body).poll_read(cx, &mut buf))
+            .await
+            .map_err(Error::decode)?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buf.truncate(n);
+        Ok(Some(buf))
+    }
 }
 
 impl ResponseWrapper {
+    /// A response which is not received, like a cached response.
+    pub(crate) fn from_parts(status: StatusCode, headers: http::HeaderMap, body: Vec<u8>) -> Result<ResponseWrapper> {
+        let mut response = Response::builder().status(status);
+        if let Some(response_headers) = response.headers_mut() {
+            *response_headers = headers;
+        }
+        let response = response.body(AsyncBody::from(body)).map_err(Error::decode)?;
+        Ok(ResponseWrapper {
+            response,
+            replay: None,
+        })
+    }
+
+    /// The request of the response, if it can be sent again.
+    pub(crate) fn take_replay(&mut self) -> Option<Replay> {
+        self.replay.take()
+    }
+
     #[cfg(feature = "json")]
     pub async fn json<T>(mut self) -> Result<T>
     where
@@ -5,8 +5,8 @@ use log::debug;
 pub fn print_request_log(request: &Request<AsyncBody>, body: Option<Vec<u8>>) {
     debug!(
         "---> {} {} {:?}",
-        request.method().to_string(),
-        request.uri().to_string(),
+        request.method(),
+        request.uri(),
         request.version(),
     );
     for (name, value) in request.headers() {
@@ -16,9 +16,8 @@ pub fn print_request_log(request: &Request<AsyncBody>, body: Option<Vec<u8>>) {
     let mut body_len = 0;
     if let Some(vec) = body {
         body_len = vec.len();
-        match String::from_utf8(vec) {
-            Ok(s) => debug!("{}", s),
-            Err(_) => {}
+        if let Ok(s) = String::from_utf8(vec) {
+            debug!("{}", s);
         }
     }
     debug!("---> END HTTP ({}-byte body)", body_len);
@@ -28,7 +27,7 @@ pub fn print_response_log(response: &Response<AsyncBody>) {
     debug!(
         "<--- {:?} {}",
         response.version(),
-        response.status().to_string(),
+        response.status(),
     );
     for (name, value) in response.headers() {
         debug!("{}: {}", name.as_str(), value.to_str().unwrap());
@@ -20,9 +20,22 @@
 //! * <a href="#query-parameters">Query Parameters</a>
 //! * <a href="#headers">Headers</a>
 //! * <a href="#form">Form</a>
+//! * <a href="#body-templates">Body Templates</a>
 //! * <a href="#json">JSON</a>
+//! * <a href="#body-and-response-formats">Body and Response Formats</a>
+//! * <a href="#codecs">Codecs</a>
+//! * <a href="#content-negotiation">Content Negotiation</a>
+//! * <a href="#streaming-responses">Streaming Responses</a>
+//! * <a href="#csv">CSV</a>
+//! * <a href="#pagination">Pagination</a>
+//! * <a href="#downloads">Downloads</a>
+//! * <a href="#progress">Progress</a>
 //! * <a href="#using-structure">Using Structure</a>
+//! * <a href="#cookies">Cookies</a>
 //! * <a href="#timeout-configuration">Timeout Configuration</a>
+//! * <a href="#profiles">Profiles</a>
+//! * <a href="#caching">Caching</a>
+//! * <a href="#compression">Compression</a>
 //! * <a href="#params">Params</a>
 //! * <a href="#error-handling">Error Handling</a>
 //! * <a href="#logs">Logs</a>
@@ -122,6 +135,39 @@
 //! `dxx` will replace `{owner}` and `feignhttp` will replace `{repo}` , the url to be send will be
 //! `https://api.github.com/repos/dxx/feignhttp`. You can specify a path name like `#[path("owner")]`.
 //!
+//! Placeholders in a literal url or path are checked against `#[path]` and `#[param]` arguments at compile time.
+//! If the url is not known at compile time, a build error is returned when a placeholder has no value.
+//!
+//! Path values are percent-encoded as a single path segment, so a value like `a/b` or `?x=1` cannot change the request
+//! target. Use `#[path(raw)]` for values that legitimately contain slashes:
+//!
+//! ```rust, no_run
+//! use feignhttp::get;
+//!
+//! #[get("https://raw.githubusercontent.com/dxx/feignhttp/{file}")]
+//! async fn file(#[path(raw)] file: &str) -> feignhttp::Result<String> {}
+//! ```
+//!
+//! The url and path are [RFC 6570](https://www.rfc-editor.org/rfc/rfc6570) URI templates. Besides `{var}`,
+//! reserved expansion `{+var}` keeps slashes, `{/list*}` expands a list as path segments, and `{?page,per_page}`
+//! expands query parameters, skipping the ones without a value:
+//!
+//! ```rust, no_run
+//! use feignhttp::get;
+//!
+//! #[get("https://api.github.com/repos/{owner}/{repo}/issues{?page,per_page}")]
+//! async fn issues(
+//!     #[path] owner: &str,
+//!     #[path] repo: &str,
+//!     #[param] page: u32,
+//!     #[param] per_page: u32,
+//! ) -> feignhttp::Result<String> {}
+//! ```
+//!
+//! Variables are filled by `#[path]` arguments and `#[url_path]` fields first, then by `#[param]` values.
+//!
+//! Header values containing CR or LF are rejected with a build error.
+//!
 //! ## URL
 //!
 //! You can use constant to maintain all urls of request:
@@ -152,6 +198,21 @@
 //! ) -> feignhttp::Result<String> {}
 //! ```
 //!
+//! Placeholders like `${NAME}` or `${NAME:default}` in url, path, headers and config metadata are resolved from environment
+//! variables at call time, so the same binary can be deployed to different environments:
+//!
+//! ```rust, no_run
+//! use feignhttp::get;
+//!
+//! #[get(url = "${GITHUB_URL:https://api.github.com}", headers = "authorization: token ${GITHUB_TOKEN}")]
+//! async fn user() -> feignhttp::Result<String> {}
+//! ```
+//!
+//! A config error is returned if a variable without default is not set.
+//!
+//! The path is joined to the url keeping the base path, so `https://xxx/api/v2/` and `/users` become
+//! `https://xxx/api/v2/users`. A query string of the url is merged with the query of the path.
+//!
 //! See [here](https://github.com/dxx/feignhttp/blob/HEAD/examples/url.rs) for more examples.
 //!
 //! ## Query Parameters
@@ -181,6 +242,18 @@
 //!
 //! **Note**: A function parameter without `query` attribute will as a query parameter by default.
 //!
+//! Fixed query parameters can be specified by `query` metadata, values can be replaced by `param`:
+//!
+//! ```rust, no_run
+//! use feignhttp::get;
+//!
+//! #[get("https://httpbin.org/anything", query = "api-version=2023-01-01&format={format}")]
+//! async fn anything(#[param] format: &str, #[query] page: u32) -> feignhttp::Result<String> {}
+//! ```
+//!
+//! The query in metadata comes first, then the query of the structure and `#[query]` arguments. The query of a method
+//! in `#[feign]` is appended to the query of `#[feign]`.
+//!
 //! ## Headers
 //!
 //! Using `header` to specify request header:
@@ -250,6 +323,24 @@
 //! Before send request, a header `content-type: application/x-www-form-urlencoded` will be added automatically.
 //! See [here](https://github.com/dxx/feignhttp/blob/HEAD/examples/form.rs) for more examples.
 //!
+//! ## Body Templates
+//!
+//! Small request bodies can be written as a template by `body` metadata, `{name}` placeholders are replaced by `param`:
+//!
+//! ```rust, no_run
+//! use feignhttp::post;
+//!
+//! #[post(
+//!     url = "https://httpbin.org/anything",
+//!     body = r#"{"user":"{name}","id":{id}}"#,
+//!     content_type = "application/json"
+//! )]
+//! async fn create_user(#[param] name: &str, #[param] id: u32) -> feignhttp::Result<String> {}
+//! ```
+//!
+//! Values are escaped for the `content_type`, as JSON string content for `application/json` and form encoded for
+//! `application/x-www-form-urlencoded`. The default content type is `text/plain`.
+//!
 //! ## JSON
 //!
 //! [Serde](https://docs.rs/serde) is a framework for serializing and deserializing Rust data structures. When use json, you should add serde in `Cargo.toml`:
@@ -340,6 +431,265 @@
 //! Before send request, a header `content-type: application/json` will be added automatically.
 //! See [here](https://github.com/dxx/feignhttp/blob/HEAD/examples/json.rs) for a complete example.
 //!
+//! ## Body and Response Formats
+//!
+//! The format of a body can be specified by `#[body(json)]`, `#[body(text)]` or `#[body(bytes)]`. Without a format, a body
+//! or return type is sent or received by [`IntoBody`](https://docs.rs/feignhttp/latest/feignhttp/trait.IntoBody.html)
+//! and [`FromResponse`](https://docs.rs/feignhttp/latest/feignhttp/trait.FromResponse.html) if they are implemented,
+//! or as JSON otherwise. `Json<T>`, `Text` and `Bytes` wrappers make the format explicit:
+//!
+//! ```rust, no_run
+//! use feignhttp::{post, Bytes, Text};
+//!
+//! type Message = String;
+//!
+//! #[post("https://httpbin.org/anything")]
+//! async fn send_message(#[body(text)] message: Message) -> feignhttp::Result<Text> {}
+//!
+//! #[post("https://httpbin.org/anything")]
+//! async fn send_data(#[body] data: Bytes) -> feignhttp::Result<Bytes> {}
+//! ```
+//!
+//! `String`, `&str`, `Box<str>` and `Cow<str>` are text, `Vec<u8>` and `&[u8]` are bytes. Implement `IntoBody` or
+//! `FromResponse` for other formats.
+//!
+//! Binary formats are enabled by the `msgpack`, `cbor` and `protobuf` features. They are selected by
+//! `#[body(msgpack)]`, `#[body(cbor)]` and `#[body(protobuf)]`, or the `MsgPack<T>`, `Cbor<T>` and `Protobuf<T>`
+//! wrappers. A Protobuf message is a type implementing `prost::Message`.
+//!
+//! ## Codecs
+//!
+//! Bodies and return values which don't implement `IntoBody` or `FromResponse` are serialized by a codec, JSON by
+//! default. `codec = "xml"` in a function or `#[feign]` selects the XML codec, which requires the `xml` feature.
+//! `codec = "msgpack"` and `codec = "cbor"` select the MessagePack and CBOR codecs in the same way:
+//!
+//! ```rust, no_run
+//! use feignhttp::post;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     id: i32,
+//! }
+//!
+//! # #[cfg(feature = "xml")]
+//! #[post("https://httpbin.org/anything", codec = "xml")]
+//! async fn post_user(#[body] user: User) -> feignhttp::Result<User> {}
+//! ```
+//!
+//! The codec also sets the `content-type` and `accept` headers. A custom codec is a type implementing
+//! [`Encoder`](https://docs.rs/feignhttp/latest/feignhttp/codec/trait.Encoder.html) and
+//! [`Decoder`](https://docs.rs/feignhttp/latest/feignhttp/codec/trait.Decoder.html), like `codec = "MyCodec"`.
+//!
+//! ## Content Negotiation
+//!
+//! `consumes` sets the `content-type` header of the request and `produces` sets the `accept` header, in a function
+//! or in `#[feign]`:
+//!
+//! ```rust, no_run
+//! use feignhttp::post;
+//!
+//! #[post("https://httpbin.org/anything", consumes = "application/xml", produces = "application/json")]
+//! async fn post_xml(#[body] xml: &str) -> feignhttp::Result<String> {}
+//! ```
+//!
+//! Without `produces`, the `accept` header is the media type of the return type if it has one, like `application/json`
+//! for a JSON return type. The `content-type` of the response must match the media type, otherwise a decode error is
+//! returned. A response without `content-type` is accepted.
+//!
+//! ## Streaming Responses
+//!
+//! A `JsonLines<T>` return type decodes a newline-delimited JSON response line by line as it arrives. It is a
+//! `Stream` of `feignhttp::Result<T>`, an invalid line is an error with its line number:
+//!
+//! ```rust, no_run
+//! use feignhttp::get;
+//! # #[cfg(feature = "json")]
+//! use feignhttp::JsonLines;
+//! use futures_util::StreamExt;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Event {
+//!     id: i32,
+//! }
+//!
+//! # #[cfg(feature = "json")]
+//! #[get("https://example.com/events")]
+//! async fn watch() -> feignhttp::Result<JsonLines<Event>> {}
+//!
+//! # #[cfg(feature = "json")]
+//! #[tokio::main]
+//! async fn main() -> feignhttp::Result<()> {
+//!     let mut events = watch().await?.max_line_length(64 * 1024);
+//!     while let Some(event) = events.next().await {
+//!         println!("{}", event?.id);
+//!     }
+//!     Ok(())
+//! }
+//! # #[cfg(not(feature = "json"))]
+//! # fn main() {}
+//! ```
+//!
+//! A line longer than `max_line_length`, 1 MiB by default, ends the stream with a decode error.
+//!
+//! A `JsonArray<T>` return type decodes the items of a top-level JSON array one at a time, only the item being
+//! received is buffered. It is a `Stream` of `feignhttp::Result<T>` like `JsonLines<T>`, with `max_item_length`.
+//!
+//! An `EventStream<T>` return type receives server-sent events of a `text/event-stream` response. The data of an
+//! event is a `String`, or decoded as JSON by `EventStream<Json<T>>`. When the connection is closed, the request is
+//! sent again after the `retry` interval of the server with the `last-event-id` header:
+//!
+//! ```rust, no_run
+//! use feignhttp::{get, EventStream};
+//! use futures_util::StreamExt;
+//!
+//! #[get("https://example.com/builds/{id}/logs")]
+//! async fn build_logs(#[path] id: u32) -> feignhttp::Result<EventStream> {}
+//!
+//! #[tokio::main]
+//! async fn main() -> feignhttp::Result<()> {
+//!     let mut events = build_logs(1).await?;
+//!     while let Some(event) = events.next().await {
+//!         let event = event?;
+//!         println!("{}: {}", event.event, event.data);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## CSV
+//!
+//! With the `csv` feature, a `Csv<T>` return type deserializes the rows of a CSV response into a `Vec<T>` by serde, and
+//! a `CsvStream<T>` return type is a `Stream` of rows decoded as they arrive. The first row is a header row which names
+//! the fields by default. The delimiter, quote and header row are set by `csv_delimiter`, `csv_quote` and `csv_headers`:
+//!
+//! ```rust, no_run
+//! use feignhttp::get;
+//! # #[cfg(feature = "csv")]
+//! use feignhttp::Csv;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Sale {
+//!     region: String,
+//!     amount: f64,
+//! }
+//!
+//! # #[cfg(feature = "csv")]
+//! #[get("https://example.com/sales.tsv", csv_delimiter = "\t", csv_quote = "'", csv_headers = "false")]
+//! async fn sales() -> feignhttp::Result<Csv<Sale>> {}
+//!
+//! # #[cfg(feature = "csv")]
+//! #[tokio::main]
+//! async fn main() -> feignhttp::Result<()> {
+//!     for sale in sales().await?.iter() {
+//!         println!("{}: {}", sale.region, sale.amount);
+//!     }
+//!     Ok(())
+//! }
+//! # #[cfg(not(feature = "csv"))]
+//! # fn main() {}
+//! ```
+//!
+//! A row which fails to decode is an error with its row number, `CsvStream<T>` goes on with the next row.
+//!
+//! ## Pagination
+//!
+//! A list function with `#[paginate]` returns a `Pages<T>` stream of the items of all pages instead of one page. Pages
+//! are requested as the items are consumed, until the last page:
+//!
+//! * `#[paginate(link_header)]` follows the `rel="next"` url of the `Link` header, like the GitHub API.
+//! * `#[paginate(cursor = "next_cursor", param = "cursor", items = "items")]` sends the `next_cursor` field of the
+//!   response body by the `cursor` query parameter, until no cursor is returned. The items are the `items` field.
+//! * `#[paginate(page = "page")]` increases the `page` query parameter from the page of the first request, or 1, until a
+//!   page has no items.
+//!
+//! ```rust, no_run
+//! use feignhttp::get;
+//! use futures_util::StreamExt;
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Contributor {
+//!     login: String,
+//! }
+//!
+//! # #[cfg(feature = "json")]
+//! #[get("https://api.github.com/repos/{owner}/{repo}/contributors")]
+//! #[paginate(link_header, max_pages = 10)]
+//! async fn contributors(
+//!     #[path] owner: &str,
+//!     #[path] repo: &str,
+//!     #[query] per_page: u32,
+//! ) -> feignhttp::Result<Vec<Contributor>> {}
+//!
+//! # #[cfg(feature = "json")]
+//! #[tokio::main]
+//! async fn main() -> feignhttp::Result<()> {
+//!     let mut contributors = contributors("dxx", "feignhttp", 100);
+//!     while let Some(contributor) = contributors.next().await {
+//!         println!("{}", contributor?.login);
+//!     }
+//!     Ok(())
+//! }
+//! # #[cfg(not(feature = "json"))]
+//! # fn main() {}
+//! ```
+//!
+//! At most `max_pages` pages are requested, 100 by default, the stream ends with an error if there are more pages.
+//! A paginated request can't have a body.
+//!
+//! ## Downloads
+//!
+//! A function with a `#[download]` argument writes the response body to the file at that path, and returns a `Download`
+//! with the path and the length of the file:
+//!
+//! ```rust, no_run
+//! use feignhttp::{get, Download};
+//!
+//! #[get("https://github.com/dxx/feignhttp/archive/refs/tags/{tag}.tar.gz")]
+//! async fn archive(#[path] tag: &str, #[download] dest: &str) -> feignhttp::Result<Download> {}
+//!
+//! #[tokio::main]
+//! async fn main() -> feignhttp::Result<()> {
+//!     let download = archive("v0.5.2", "feignhttp.tar.gz").await?;
+//!     println!("{} bytes", download.len);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The body is written to a `.part` file, which is renamed to the file when the download is complete. If the response
+//! has a strong `ETag`, an interrupted download is resumed by the next call with `Range` and `If-Range` headers, and
+//! started again if the file has changed. If the path is a directory, the file is named by the `Content-Disposition`
+//! header or the last segment of the url, and is not resumed. A download can't have a request body.
+//!
+//! ## Progress
+//!
+//! A `#[progress]` argument is a callback which receives the `Progress` of the request as the request body is sent and the
+//! response body is received, like for a progress bar of a large transfer:
+//!
+//! ```rust, no_run
+//! use feignhttp::{post, Progress};
+//!
+//! #[post("https://httpbin.org/anything")]
+//! async fn upload(#[body] data: Vec<u8>, #[progress] progress: impl Fn(Progress)) -> feignhttp::Result<String> {}
+//!
+//! #[tokio::main]
+//! async fn main() -> feignhttp::Result<()> {
+//!     upload(vec![0; 1024 * 1024], |progress| {
+//!         if let Some(total) = progress.send_total {
+//!             println!("sent {} of {} bytes", progress.sent, total);
+//!         }
+//!     })
+//!     .await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The total length of the response body is `None` if it is unknown, like a compressed or chunked response. The callback
+//! is called while the request is sent, so it should return quickly.
+//!
 //! ## Using Structure
 //!
 //! Structure is a good way to manage requests. Define a structure and then define a large number of request methods：
@@ -384,6 +734,59 @@
 //!
 //! See [here](https://github.com/dxx/feignhttp/blob/HEAD/examples/struct.rs) for a complete example.
 //!
+//! ## Cookies
+//!
+//! Using `cookie` to send a cookie:
+//!
+//! ```rust, no_run
+//! use feignhttp::get;
+//!
+//! #[get("https://httpbin.org/cookies")]
+//! async fn cookies(#[cookie] session: &str) -> feignhttp::Result<String> {}
+//! ```
+//!
+//! A header `cookie: session=<value>` will be added.
+//!
+//! APIs that authenticate with a session cookie need the cookies set by a login endpoint. A
+//! [`CookieJar`](https://docs.rs/feignhttp/latest/feignhttp/struct.CookieJar.html) marked with `cookie_jar` on a
+//! structure stores `Set-Cookie` responses and sends them on subsequent calls:
+//!
+//! ```rust, no_run
+//! use feignhttp::{feign, CookieJar, Feign};
+//!
+//! #[derive(Feign)]
+//! struct Session {
+//!     #[cookie_jar]
+//!     jar: CookieJar,
+//! }
+//!
+//! #[feign(url = "https://httpbin.org")]
+//! impl Session {
+//!     #[get("/cookies/set?session=ZmVpZ25odHRw")]
+//!     async fn login(&self) -> feignhttp::Result<String> {}
+//!
+//!     #[get("/cookies")]
+//!     async fn cookies(&self) -> feignhttp::Result<String> {}
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let session = Session { jar: CookieJar::new() };
+//!     session.login().await?;
+//!     println!("cookies: {}", session.cookies().await?);
+//!
+//!     // Save the cookies in the Netscape cookie file format.
+//!     session.jar.save(std::fs::File::create("cookies.txt")?)?;
+//!     // Restore them later.
+//!     let file = std::io::BufReader::new(std::fs::File::open("cookies.txt")?);
+//!     let _session = Session { jar: CookieJar::load(file)? };
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! A cookie jar can also be attached to a request built by `HttpClient::builder()` with `cookie_jar`.
+//!
 //! ## Timeout Configuration
 //!
 //! If you need to configure the timeout, use `connect_timeout` and `timeout` to specify connect timeout and read timeout.
@@ -406,6 +809,100 @@
 //! async fn timeout() -> feignhttp::Result<String> {}
 //! ```
 //!
+//! ## Profiles
+//!
+//! Services often need their own base url, timeouts and headers. Register a named profile at startup and reference it with
+//! `profile`, the profile supplies defaults that the metadata in the attribute can override:
+//!
+//! ```rust, no_run
+//! use feignhttp::{feign, register_profile, ClientConfig, Feign};
+//! use std::time::Duration;
+//!
+//! #[derive(Feign)]
+//! struct Github;
+//!
+//! #[feign(profile = "github")]
+//! impl Github {
+//!     #[get("/users/{user}")]
+//!     async fn user(&self, #[path] user: &str) -> feignhttp::Result<String> {}
+//!
+//!     #[get("/search/repositories", timeout = "30s")]
+//!     async fn search(&self, #[query] q: &str) -> feignhttp::Result<String> {}
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> feignhttp::Result<()> {
+//!     register_profile("github", ClientConfig {
+//!         base_url: Some("https://api.github.com".to_string()),
+//!         timeout: Some(Duration::from_secs(5)),
+//!         ..Default::default()
+//!     });
+//!     let user = Github.user("dxx").await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The `base_url` is used when the attribute has no url. `config` is an alias of `profile`.
+//!
+//! Profiles can also be loaded from a TOML or YAML file with `feignhttp::load_config("client.toml")`, enable the `toml` or
+//! `yaml` feature to use it:
+//!
+//! ```toml
+//! [github]
+//! base_url = "https://api.github.com"
+//! connect_timeout = 3000
+//! timeout = "5s"
+//!
+//! [github.headers]
+//! accept = "application/vnd.github.v3+json"
+//! ```
+//!
+//! Environment variables prefixed with the name in upper case override the profile, like `GITHUB__BASE_URL`,
+//! `GITHUB__TIMEOUT=10s` or `GITHUB__HEADERS__ACCEPT=application/json`. Durations can be a number of milliseconds or a
+//! string like `300ms`, `5s` or `1m30s`.
+//!
+//! ## Caching
+//!
+//! GET responses can be cached with the `cache` metadata, in memory with `cache = "memory"`, or in files with
+//! `cache = "disk"` in the temporary directory or `cache = "disk:<dir>"` in a directory:
+//!
+//! ```rust, no_run
+//! use feignhttp::get;
+//!
+//! #[get("https://example.com/settings", cache = "memory")]
+//! async fn settings() -> feignhttp::Result<String> {}
+//! ```
+//!
+//! A response is cached if it has a `Cache-Control: max-age`, `Expires`, `ETag` or `Last-Modified` header and no
+//! `Cache-Control: no-store`. It is returned without a request while it is fresh. A stale response is revalidated with
+//! `If-None-Match` and `If-Modified-Since` headers, and it is returned again if the server responds `304 Not Modified`.
+//! A request with other values of the headers named by `Vary` is not served by the cached response. `Cache-Control:
+//! no-cache` in the request revalidates the cached response, and `no-store` skips the cache. The `cache` can also be set
+//! by a profile.
+//!
+//! ## Compression
+//!
+//! Enable one or more of the `gzip`, `deflate`, `brotli` and `zstd` features to negotiate `Accept-Encoding` and decode
+//! compressed responses transparently:
+//!
+//! ```toml
+//! feignhttp = { version = "<version>", features = ["gzip", "brotli"] }
+//! ```
+//!
+//! **Note**: `zstd` is negotiated by the isahc backend only, reqwest does not support it.
+//!
+//! APIs which accept compressed uploads can use the `compress` metadata, the request body will be compressed and a header
+//! `content-encoding` will be added:
+//!
+//! ```rust, no_run
+//! use feignhttp::post;
+//!
+//! #[post("https://httpbin.org/anything", compress = "gzip")]
+//! async fn upload(#[body] text: String) -> feignhttp::Result<String> {}
+//! ```
+//!
+//! The supported encodings are `gzip`, `deflate` and `br`, each requires the matching feature.
+//!
 //! ## Params
 //!
 //! Sometimes you need dynamic values, like config or others. `param` is designed to support such ability. You can use
@@ -438,7 +935,7 @@
 //! }
 //! ```
 //!
-//! **Note**: `param` can't replace placeholder in url or path.
+//! `param` can also fill placeholders in url or path, see [Paths](#paths).
 //!
 //! ## Error Handling
 //!
@@ -473,12 +970,12 @@
 //! ```rust, no_run
 //! use feignhttp::get;
 //!
-//! #[get(url = "https://httpbin.org/delay/3", timeout = "abc")]
-//! async fn config_error() -> feignhttp::Result<()> {}
+//! #[get(url = "https://httpbin.org/delay/3", timeout = "{time}")]
+//! async fn config_error(#[param] time: &str) -> feignhttp::Result<()> {}
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     match config_error().await {
+//!     match config_error("abc").await {
 //!         Err(err) => {
 //!             // Parse config error.
 //!             if err.is_config_error() {
@@ -489,7 +986,8 @@
 //!     }
 //! }
 //! ```
-//! When parsing the configuration, an error is thrown if the value is incorrect. `timeout` is an integer type, when parse `abc` to integer will throw an error.
+//! When parsing the configuration, an error is thrown if the value is incorrect. `timeout` is a duration, when parse `abc` to duration will throw an error.
+//! A literal config value like `timeout = "abc"` is checked at compile time instead.
 //!
 //! HTTP status is an importmant info about response. The status code can tell whether the client or server is abnormal.
 //! The following is an example of handling through HTTP status:
@@ -542,11 +1040,34 @@
 //! * **reqwest-client** *(default)*: Use `reqwest` as the HTTP backend
 //! * **isahc-client**: Use `isahc` as the HTTP backend
 //! * **json**: Enable json serialization and deserialization
+//! * **xml**: Enable the xml codec
+//! * **msgpack**: Enable the MessagePack codec
+//! * **cbor**: Enable the CBOR codec
+//! * **protobuf**: Enable Protobuf bodies and responses
+//! * **csv**: Enable CSV responses
 //! * **log**: Enable request and response logs
+//! * **gzip**: Enable gzip response decoding and request compression
+//! * **deflate**: Enable deflate response decoding and request compression
+//! * **brotli**: Enable brotli response decoding and request compression
+//! * **zstd**: Enable zstd response decoding (isahc only)
+//! * **toml**: Enable loading profiles from TOML files
+//! * **yaml**: Enable loading profiles from YAML files
 
+mod body;
+mod cache;
+mod compress;
+mod config;
+mod cookie;
+#[cfg(feature = "csv")]
+mod csv;
+mod download;
 mod error;
 mod http;
 mod macros;
+mod page;
+mod progress;
+mod stream;
+mod template;
 
 #[cfg(feature = "reqwest-client")]
 mod reqwest;
@@ -558,6 +1079,8 @@ mod isahc;
 #[cfg(feature = "isahc-client")]
 pub use crate::isahc::*;
 
+pub mod codec;
+
 #[doc(hidden)]
 pub mod ser;
 #[doc(hidden)]
@@ -566,12 +1089,33 @@ pub mod util;
 pub use feignhttp_codegen::*;
 use std::collections::HashMap;
 
+pub use crate::body::{Bytes, Cbor, FromResponse, IntoBody, Json, MsgPack, Protobuf, Text};
+pub use crate::codec::{Decoder, Encoder};
+pub use crate::config::{load_config, parse_duration, register_profile, ClientConfig};
+pub use crate::cookie::CookieJar;
+#[cfg(feature = "csv")]
+pub use crate::csv::{Csv, CsvConfig, CsvStream, FromCsv};
+pub use crate::download::Download;
 pub use crate::error::{Error, ErrorKind, Result};
 pub use crate::http::*;
+pub use crate::page::{Pages, Pagination};
+pub use crate::progress::Progress;
+#[cfg(feature = "json")]
+pub use crate::stream::{JsonArray, JsonLines};
+pub use crate::stream::{Event, EventData, EventStream};
+pub use crate::template::{TemplateValue, UriTemplate};
 
 pub trait FeignClient {
     fn param_map(&self) -> HashMap<&str, String>;
-    fn header_map(&self) -> HashMap<std::borrow::Cow<str>, String>;
-    fn path_map(&self) -> HashMap<&str, String>;
+    fn header_map(&self) -> HashMap<std::borrow::Cow<'_, str>, String>;
+    fn path_map(&self) -> HashMap<&str, TemplateValue>;
     fn query_map(&self) -> Vec<(&str, String)>;
+
+    fn cookie_map(&self) -> Vec<(&str, String)> {
+        Vec::new()
+    }
+
+    fn cookie_jar(&self) -> Option<CookieJar> {
+        None
+    }
 }
@@ -0,0 +1,241 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::http::{HttpResponse, Replay};
+use crate::{RequestWrapper, ResponseWrapper};
+use futures_core::Stream;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use url::Url;
+
+/// How the request of the next page is made, set by `#[paginate]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pagination {
+    /// Follow the `rel="next"` url of the `Link` header.
+    LinkHeader,
+    /// Send the cursor of the response body by a query parameter, until no cursor is returned.
+    Cursor { param: &'static str },
+    /// Increase a page number query parameter, until a page has no items.
+    Page { param: &'static str },
+}
+
+/// The items of a page and the cursor of the next page.
+#[doc(hidden)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub cursor: Option<String>,
+}
+
+/// A cursor in a response body, a string or a number.
+#[doc(hidden)]
+pub struct Cursor(pub String);
+
+impl<'de> Deserialize<'de> for Cursor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct CursorVisitor;
+
+        impl Visitor<'_> for CursorVisitor {
+            type Value = Cursor;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string or a number")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Cursor, E> {
+                Ok(Cursor(v.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Cursor, E> {
+                Ok(Cursor(v.to_string()))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<Cursor, E> {
+                Ok(Cursor(v.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(CursorVisitor)
+    }
+}
+
+type PageFuture<T> = Pin<Box<dyn Future<Output = Result<Page<T>>> + Send>>;
+
+type DecodePage<T> = Arc<dyn Fn(ResponseWrapper) -> PageFuture<T> + Send + Sync>;
+
+type FetchFuture<T> = Pin<Box<dyn Future<Output = Result<(Replay, Page<T>, Option<String>)>> + Send>>;
+
+/// A stream of the items of a paginated list, returned by a function with `#[paginate]`.
+///
+/// Pages are requested as the items are consumed. An error ends the stream, and so does reaching
+/// `max_pages` while there are more pages.
+pub struct Pages<T> {
+    pagination: Pagination,
+    max_pages: usize,
+    decode: DecodePage<T>,
+    next: Option<Replay>,
+    fetching: Option<FetchFuture<T>>,
+    items: std::vec::IntoIter<T>,
+    error: Option<Error>,
+    pages: usize,
+}
+
+// Nothing is pinned structurally.
+impl<T> Unpin for Pages<T> {}
+
+impl<T: 'static> Pages<T> {
+    /// The default maximum number of pages requested.
+    pub const DEFAULT_MAX_PAGES: usize = 100;
+
+    #[doc(hidden)]
+    pub fn new<F>(request: Result<RequestWrapper>, pagination: Pagination, decode: F) -> Self
+    where
+        F: Fn(ResponseWrapper) -> PageFuture<T> + Send + Sync + 'static,
+    {
+        let (next, error) = match request {
+            Ok(request) => (Some(request.snapshot(None)), None),
+            Err(e) => (None, Some(e)),
+        };
+        Pages {
+            pagination,
+            max_pages: Self::DEFAULT_MAX_PAGES,
+            decode: Arc::new(decode),
+            next,
+            fetching: None,
+            items: Vec::new().into_iter(),
+            error,
+            pages: 0,
+        }
+    }
+
+    /// Set the maximum number of pages requested, more pages end the stream with an error.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    fn fetch(&self, replay: Replay) -> FetchFuture<T> {
+        let decode = self.decode.clone();
+        Box::pin(async move {
+            let response = replay.send(Vec::new()).await?;
+            let link = next_link(response.headers(), &replay.url);
+            let page = decode(response).await?;
+            Ok((replay, page, link))
+        })
+    }
+
+    /// The request of the page after a received page, `None` if it is the last page.
+    fn next_request(&self, mut replay: Replay, page: &Page<T>, link: Option<String>) -> Result<Option<Replay>> {
+        match self.pagination {
+            Pagination::LinkHeader => match link {
+                Some(link) => replay.url = link,
+                None => return Ok(None),
+            },
+            Pagination::Cursor { param } => match page.cursor.as_deref() {
+                Some(cursor) if !cursor.is_empty() => replay.url = set_query(&replay.url, param, cursor)?,
+                _ => return Ok(None),
+            },
+            Pagination::Page { param } => {
+                if page.items.is_empty() {
+                    return Ok(None);
+                }
+                // A request without the page parameter gets the first page.
+                let number = match get_query(&replay.url, param) {
+                    Some(number) => number.parse::<u64>().map_err(|_| {
+                        Error::build(format!("invalid page number {}={}", param, number))
+                    })?,
+                    None => 1,
+                };
+                replay.url = set_query(&replay.url, param, &(number + 1).to_string())?;
+            }
+        }
+        Ok(Some(replay))
+    }
+}
+
+impl<T: 'static> Stream for Pages<T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(item) = this.items.next() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if let Some(e) = this.error.take() {
+                return Poll::Ready(Some(Err(e)));
+            }
+            let fetching = match this.fetching.take() {
+                Some(fetching) => fetching,
+                None => match this.next.take() {
+                    Some(replay) => this.fetch(replay),
+                    None => return Poll::Ready(None),
+                },
+            };
+            let fetching = this.fetching.insert(fetching);
+            let result = ready!(fetching.as_mut().poll(cx));
+            this.fetching = None;
+            let (replay, page, link) = match result {
+                Ok(page) => page,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            };
+            this.pages += 1;
+            match this.next_request(replay, &page, link) {
+                Ok(Some(_)) if this.pages >= this.max_pages => {
+                    this.error = Some(Error::new(
+                        ErrorKind::Request,
+                        Some(format!("pagination exceeds the maximum of {} pages", this.max_pages)),
+                    ));
+                }
+                Ok(next) => this.next = next,
+                Err(e) => this.error = Some(e),
+            }
+            this.items = page.items.into_iter();
+        }
+    }
+}
+
+/// The url of the `rel="next"` link of `Link` headers, resolved against the request url.
+fn next_link(headers: &http::HeaderMap, base: &str) -> Option<String> {
+    for value in headers.get_all(http::header::LINK).iter().filter_map(|v| v.to_str().ok()) {
+        let mut rest = value;
+        while let Some(start) = rest.find('<') {
+            let end = start + rest[start..].find('>')?;
+            let target = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+            let params = &rest[..rest.find('<').unwrap_or(rest.len())];
+            let is_next = params.split(';').any(|param| match param.split_once('=') {
+                Some((key, value)) if key.trim().eq_ignore_ascii_case("rel") => value
+                    .trim()
+                    .trim_end_matches(',')
+                    .trim_matches('"')
+                    .split_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case("next")),
+                _ => false,
+            });
+            if is_next {
+                return Url::parse(base).and_then(|base| base.join(target)).ok().map(String::from);
+            }
+        }
+    }
+    None
+}
+
+fn get_query(url: &str, name: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let value = url.query_pairs().find(|(key, _)| key == name)?.1;
+    Some(value.into_owned())
+}
+
+/// Set a query parameter of the url, replacing the parameters of the same name.
+fn set_query(url: &str, name: &str, value: &str) -> Result<String> {
+    let mut url = Url::parse(url).map_err(Error::build)?;
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != name)
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    url.query_pairs_mut().clear().extend_pairs(pairs).append_pair(name, value);
+    Ok(url.into())
+}
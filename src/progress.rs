@@ -0,0 +1,154 @@
+use futures_core::Stream;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// The progress of a request, reported to the callback of a `#[progress]` argument as the bodies are
+/// transferred.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// The bytes of the request body sent.
+    pub sent: u64,
+    /// The length of the request body, `None` if the request has no body.
+    pub send_total: Option<u64>,
+    /// The bytes of the response body received.
+    pub received: u64,
+    /// The length of the response body, `None` if it is unknown, like a compressed or chunked response.
+    pub receive_total: Option<u64>,
+}
+
+pub(crate) type ProgressFn = Arc<dyn Fn(Progress) + Send + Sync>;
+
+/// The progress of a sent request, shared by the request body and the response body.
+#[derive(Clone)]
+pub(crate) struct Tracker {
+    callback: ProgressFn,
+    progress: Arc<Mutex<Progress>>,
+}
+
+impl Tracker {
+    pub(crate) fn new(callback: ProgressFn) -> Self {
+        Tracker {
+            callback,
+            progress: Arc::new(Mutex::new(Progress::default())),
+        }
+    }
+
+    /// Update the progress and report it, the lock is not held by the callback.
+    fn update(&self, f: impl FnOnce(&mut Progress)) {
+        let progress = {
+            let mut progress = self.progress.lock().unwrap();
+            f(&mut progress);
+            *progress
+        };
+        (self.callback)(progress);
+    }
+
+    pub(crate) fn send_total(&self, total: u64) {
+        self.update(|progress| progress.send_total = Some(total));
+    }
+
+    /// Set the length of the response body from the headers, when the response is received.
+    pub(crate) fn receive_total(&self, headers: &http::HeaderMap) {
+        // The length is unknown if the body is decompressed.
+        let total = match headers.contains_key(http::header::CONTENT_ENCODING) {
+            true => None,
+            false => headers
+                .get(http::header::CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok()?.parse().ok()),
+        };
+        self.update(|progress| progress.receive_total = total);
+    }
+
+    fn transferred(&self, direction: Direction, len: usize) {
+        if len == 0 {
+            return;
+        }
+        self.update(|progress| match direction {
+            Direction::Send => progress.sent += len as u64,
+            Direction::Receive => progress.received += len as u64,
+        });
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Direction {
+    Send,
+    Receive,
+}
+
+/// A body which reports its bytes as they are read, a stream of chunks or a reader.
+pub(crate) struct Counted<B> {
+    inner: B,
+    tracker: Tracker,
+    direction: Direction,
+}
+
+impl<B> Counted<B> {
+    pub(crate) fn new(inner: B, tracker: Tracker, direction: Direction) -> Self {
+        Counted {
+            inner,
+            tracker,
+            direction,
+        }
+    }
+}
+
+impl<S, T, E> Stream for Counted<S>
+where
+    S: Stream<Item = std::result::Result<T, E>> + Unpin,
+    T: AsRef<[u8]>,
+{
+    type Item = std::result::Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = Pin::new(&mut self.inner).poll_next(cx);
+        if let Poll::Ready(Some(Ok(chunk))) = &item {
+            self.tracker.transferred(self.direction, chunk.as_ref().len());
+        }
+        item
+    }
+}
+
+#[cfg(feature = "isahc-client")]
+impl<R: futures_io::AsyncRead + Unpin> futures_io::AsyncRead for Counted<R> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let read = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = &read {
+            self.tracker.transferred(self.direction, *n);
+        }
+        read
+    }
+}
+
+/// A request body sent in chunks, so that the progress is reported as it is sent.
+#[cfg(feature = "reqwest-client")]
+pub(crate) struct Chunked {
+    data: Vec<u8>,
+    pos: usize,
+}
+
+#[cfg(feature = "reqwest-client")]
+impl Chunked {
+    /// The size of a chunk of a request body.
+    const CHUNK_SIZE: usize = 8 * 1024;
+
+    pub(crate) fn new(data: Vec<u8>) -> Self {
+        Chunked { data, pos: 0 }
+    }
+}
+
+#[cfg(feature = "reqwest-client")]
+impl Stream for Chunked {
+    type Item = std::io::Result<Vec<u8>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.pos >= self.data.len() {
+            return Poll::Ready(None);
+        }
+        let end = self.data.len().min(self.pos + Self::CHUNK_SIZE);
+        let chunk = self.data[self.pos..end].to_vec();
+        self.pos = end;
+        Poll::Ready(Some(Ok(chunk)))
+    }
+}
@@ -1,12 +1,20 @@
 #[cfg(feature = "log")]
 use super::log::{print_request_log, print_response_log};
 use crate::{
+    body::IntoBody,
+    cache::HttpCache,
+    compress::compress,
+    cookie::{cookie_header, CookieJar},
     error::{Error, ErrorKind, Result},
-    http::{HttpConfig, HttpRequest, HttpResponse},
+    http::{HttpConfig, HttpRequest, HttpResponse, Replay},
     map,
+    progress::{Chunked, Counted, Direction, ProgressFn, Tracker},
 };
 use async_trait::async_trait;
-use http::StatusCode;
+use http::{
+    header::{CONTENT_LENGTH, SET_COOKIE},
+    StatusCode,
+};
 use reqwest::{Body, Client, Method, RequestBuilder, Response};
 use std::str::FromStr;
 use std::time::Duration;
@@ -17,12 +25,19 @@ use url::Url;
 pub struct RequestWrapper {
     url: Url,
     headers: HashMap<String, String>,
+    cookies: Vec<(String, String)>,
+    cookie_jar: Option<CookieJar>,
+    compress: Option<String>,
+    method: String,
+    config: HttpConfig,
+    progress: Option<ProgressFn>,
     request: RequestBuilder,
 }
 
 /// A wrapper of HTTP response.
 pub struct ResponseWrapper {
     response: Response,
+    replay: Option<Replay>,
 }
 
 impl HttpRequest for RequestWrapper {
@@ -34,7 +49,7 @@ impl HttpRequest for RequestWrapper {
     }
 
     fn query(mut self, query: Vec<(&str, String)>) -> Self {
-        if query.len() == 0 {
+        if query.is_empty() {
             return self;
         }
         let mut request = self.request;
@@ -48,6 +63,18 @@ impl HttpRequest for RequestWrapper {
         self.request = request;
         self
     }
+
+    fn cookies(mut self, cookies: Vec<(&str, String)>) -> Self {
+        for (k, v) in cookies {
+            self.cookies.push((k.to_string(), v));
+        }
+        self
+    }
+
+    fn cookie_jar(mut self, cookie_jar: CookieJar) -> Self {
+        self.cookie_jar = Some(cookie_jar);
+        self
+    }
 }
 
 impl RequestWrapper {
@@ -61,6 +88,12 @@ impl RequestWrapper {
             url,
             headers: map!(
                 "user-agent".to_string() => "Feign HTTP".to_string()),
+            cookies: Vec::new(),
+            cookie_jar: None,
+            compress: None,
+            method: method.to_string(),
+            config: HttpConfig::default(),
+            progress: None,
             request,
         })
     }
@@ -86,11 +119,25 @@ impl RequestWrapper {
             url,
             headers: map!(
                 "user-agent".to_string() => "Feign HTTP".to_string()),
+            cookies: Vec::new(),
+            cookie_jar: None,
+            compress: config.compress.clone(),
+            method: method.to_string(),
+            config,
+            progress: None,
             request,
         })
     }
 
     fn set_header(mut self) -> Self {
+        if let Some(cookie) = cookie_header(
+            self.headers.get("cookie"),
+            &self.cookies,
+            self.cookie_jar.as_ref(),
+            &self.url,
+        ) {
+            self.headers.insert("cookie".to_string(), cookie);
+        }
         let mut request = self.request;
         for (k, v) in &self.headers {
             request = request.header(k.as_str(), v);
@@ -100,27 +147,95 @@ impl RequestWrapper {
     }
 
     fn set_header_if_absent(&mut self, k: &str, v: String) {
-        if let None = self.headers.get(k) {
+        if !self.headers.contains_key(k) {
             self.headers.insert(k.to_string(), v);
         }
     }
 
-    async fn send_body(self, body: Option<Body>) -> Result<ResponseWrapper> {
+    fn replay(&self, body: &Option<Vec<u8>>) -> Option<Replay> {
+        if !Replay::is_replayed(&self.headers) {
+            return None;
+        }
+        Some(self.snapshot(body.clone()))
+    }
+
+    /// A copy of the request which can be sent again.
+    pub(crate) fn snapshot(&self, body: Option<Vec<u8>>) -> Replay {
+        Replay {
+            url: self.url.to_string(),
+            method: self.method.clone(),
+            config: self.config.clone(),
+            headers: self.headers.clone(),
+            cookies: self.cookies.clone(),
+            cookie_jar: self.cookie_jar.clone(),
+            progress: self.progress.clone(),
+            body,
+        }
+    }
+
+    /// Report the progress of the request and the response to a callback.
+    pub(crate) fn progress(mut self, progress: ProgressFn) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    pub(crate) async fn send_body(self, body: Option<Vec<u8>>) -> Result<ResponseWrapper> {
+        // A GET request without a body may be served by the cache.
+        if let (Some(cache), None) = (&self.config.cache, &body) {
+            if self.method.eq_ignore_ascii_case("get") {
+                return HttpCache::parse(cache)?.send(self).await;
+            }
+        }
+        self.send_uncached(body).await
+    }
+
+    /// Send the request without the cache.
+    pub(crate) async fn send_uncached(mut self, mut body: Option<Vec<u8>>) -> Result<ResponseWrapper> {
+        let replay = self.replay(&body);
+        if let (Some(encoding), Some(data)) = (&self.compress, &body) {
+            body = Some(compress(encoding, data)?);
+            self.headers
+                .insert("content-encoding".to_string(), encoding.clone());
+        }
+
         let url = self.url.clone();
+        let cookie_jar = self.cookie_jar.clone();
+        let tracker = self.progress.clone().map(Tracker::new);
         let mut request = self.set_header().request;
 
-        if let Some(body) = body {
-            request = request.body(body);
+        // The body is sent in chunks to report the progress, the chunks are not logged.
+        let mut counted_body = None;
+        match (body, &tracker) {
+            (Some(body), Some(tracker)) => {
+                tracker.send_total(body.len() as u64);
+                request = request.header(CONTENT_LENGTH, body.len());
+                counted_body = Some(Body::wrap_stream(Counted::new(
+                    Chunked::new(body),
+                    tracker.clone(),
+                    Direction::Send,
+                )));
+            }
+            (Some(body), None) => request = request.body(Body::from(body)),
+            (None, _) => {}
         }
 
         #[cfg(feature = "log")]
         print_request_log(request.try_clone().unwrap());
 
-        return match request.send().await {
+        if let Some(body) = counted_body {
+            request = request.body(body);
+        }
+
+        match request.send().await {
             Ok(response) => {
                 #[cfg(feature = "log")]
                 print_response_log(&response);
 
+                if let Some(cookie_jar) = cookie_jar {
+                    let set_cookies = response.headers().get_all(SET_COOKIE);
+                    cookie_jar.store(set_cookies.iter().flat_map(|v| v.to_str()), &url);
+                }
+
                 let status = response.status();
 
                 // Client or server error.
@@ -128,10 +243,14 @@ impl RequestWrapper {
                     return Err(Error::status(url, status));
                 }
 
-                Ok(ResponseWrapper { response })
+                let response = match tracker {
+                    Some(tracker) => counted_response(response, tracker)?,
+                    None => response,
+                };
+                Ok(ResponseWrapper { response, replay })
             }
             Err(e) => Err(Error::new(ErrorKind::Request, Some(e))),
-        };
+        }
     }
 
     pub async fn send(self) -> Result<ResponseWrapper> {
@@ -140,7 +259,7 @@ impl RequestWrapper {
 
     pub async fn send_text(mut self, text: String) -> Result<ResponseWrapper> {
         self.set_header_if_absent("content-type", "text/plain".to_string());
-        self.send_body(Some(Body::from(text))).await
+        self.send_body(Some(text.into_bytes())).await
     }
 
     pub async fn send_form<T>(mut self, form: &T) -> Result<ResponseWrapper>
@@ -152,7 +271,7 @@ impl RequestWrapper {
             "application/x-www-form-urlencoded".to_string(),
         );
         let form = serde_urlencoded::to_string(form).map_err(Error::encode)?;
-        self.send_body(Some(Body::from(form))).await
+        self.send_body(Some(form.into_bytes())).await
     }
 
     #[cfg(feature = "json")]
@@ -162,21 +281,44 @@ impl RequestWrapper {
     {
         self.set_header_if_absent("content-type", "application/json".to_string());
         let json = serde_json::to_string(json).map_err(Error::encode)?;
-        self.send_body(Some(Body::from(json))).await
+        self.send_body(Some(json.into_bytes())).await
     }
 
     pub async fn send_vec(mut self, vec: Vec<u8>) -> Result<ResponseWrapper> {
         self.set_header_if_absent("content-type", "application/octet-stream".to_string());
-        self.send_body(Some(Body::from(vec))).await
+        self.send_body(Some(vec)).await
+    }
+
+    pub async fn send_into_body<B: IntoBody>(mut self, body: B) -> Result<ResponseWrapper> {
+        self.set_header_if_absent("content-type", body.content_type().to_string());
+        self.send_body(Some(body.into_body()?)).await
     }
 }
 
+/// A response whose body reports the progress as it is read.
+fn counted_response(response: Response, tracker: Tracker) -> Result<Response> {
+    tracker.receive_total(response.headers());
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers().clone();
+    }
+    let body = Counted::new(Box::pin(response.bytes_stream()), tracker, Direction::Receive);
+    let response = builder.body(Body::wrap_stream(body)).map_err(Error::decode)?;
+    Ok(Response::from(response))
+}
+
 #[async_trait]
 impl HttpResponse for ResponseWrapper {
     fn status(&self) -> StatusCode {
         self.response.status()
     }
 
+    fn headers(&self) -> &http::HeaderMap {
+        self.response.headers()
+    }
+
     async fn none(self) -> Result<()> {
         Ok(())
     }
@@ -189,9 +331,32 @@ impl HttpResponse for ResponseWrapper {
         let by = self.response.bytes().await.map_err(Error::decode)?;
         Ok(by.to_vec())
     }
+
+    async fn chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        let chunk = self.response.chunk().await.map_err(Error::decode)?;
+        Ok(chunk.map(|by| by.to_vec()))
+    }
 }
 
 impl ResponseWrapper {
+    /// A response which is not received, like a cached response.
+    pub(crate) fn from_parts(status: StatusCode, headers: http::HeaderMap, body: Vec<u8>) -> Result<ResponseWrapper> {
+        let mut response = http::Response::builder().status(status);
+        if let Some(response_headers) = response.headers_mut() {
+            *response_headers = headers;
+        }
+        let response = response.body(body).map_err(Error::decode)?;
+        Ok(ResponseWrapper {
+            response: Response::from(response),
+            replay: None,
+        })
+    }
+
+    /// The request of the response, if it can be sent again.
+    pub(crate) fn take_replay(&mut self) -> Option<Replay> {
+        self.replay.take()
+    }
+
     #[cfg(feature = "json")]
     pub async fn json<T>(self) -> Result<T>
     where
@@ -5,8 +5,8 @@ pub fn print_request_log(request: RequestBuilder) {
     if let Ok(request) = request.build() {
         debug!(
             "---> {} {} {:?}",
-            request.method().to_string(),
-            request.url().to_string(),
+            request.method(),
+            request.url(),
             request.version(),
         );
         for (name, value) in request.headers() {
@@ -16,9 +16,8 @@ pub fn print_request_log(request: RequestBuilder) {
         let mut body_len = 0;
         if let Some(body) = request.body() {
             let body = body.as_bytes().unwrap();
-            match String::from_utf8(body.to_vec()) {
-                Ok(s) => debug!("{}", s),
-                Err(_) => {}
+            if let Ok(s) = String::from_utf8(body.to_vec()) {
+                debug!("{}", s);
             }
             body_len = body.len();
         }
@@ -30,7 +29,7 @@ pub fn print_response_log(response: &Response) {
     debug!(
         "<--- {:?} {}",
         response.version(),
-        response.status().to_string(),
+        response.status(),
     );
     for (name, value) in response.headers() {
         debug!("{}: {}", name.as_str(), value.to_str().unwrap());
@@ -0,0 +1,674 @@
+use crate::error::{Error, Result};
+use crate::http::{HttpResponse, Replay};
+#[cfg(feature = "json")]
+use crate::Json;
+use crate::{FromResponse, ResponseWrapper, Text};
+use async_trait::async_trait;
+use futures_core::Stream;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll, Waker};
+use std::time::Duration;
+
+/// The default maximum length of a line in bytes.
+const DEFAULT_MAX_LINE_LENGTH: usize = 1024 * 1024;
+
+type ChunkFuture = Pin<Box<dyn Future<Output = (Box<ResponseWrapper>, Result<Option<Vec<u8>>>)> + Send>>;
+
+/// A line number and the line without its line break.
+type Line = (usize, Vec<u8>);
+
+/// Chunks of a response body, read as they are polled.
+pub(crate) struct Chunks {
+    state: ChunkState,
+}
+
+enum ChunkState {
+    Idle(Box<ResponseWrapper>),
+    Reading(ChunkFuture),
+    Done,
+}
+
+impl Chunks {
+    pub(crate) fn new(response: ResponseWrapper) -> Self {
+        Chunks {
+            state: ChunkState::Idle(Box::new(response)),
+        }
+    }
+
+    pub(crate) fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Vec<u8>>>> {
+        loop {
+            match std::mem::replace(&mut self.state, ChunkState::Done) {
+                ChunkState::Idle(mut response) => {
+                    self.state = ChunkState::Reading(Box::pin(async move {
+                        let chunk = response.chunk().await;
+                        (response, chunk)
+                    }));
+                }
+                ChunkState::Reading(mut future) => {
+                    return match future.as_mut().poll(cx) {
+                        Poll::Pending => {
+                            self.state = ChunkState::Reading(future);
+                            Poll::Pending
+                        }
+                        Poll::Ready((response, Ok(Some(chunk)))) => {
+                            self.state = ChunkState::Idle(response);
+                            Poll::Ready(Some(Ok(chunk)))
+                        }
+                        Poll::Ready((_, Ok(None))) => Poll::Ready(None),
+                        Poll::Ready((_, Err(e))) => Poll::Ready(Some(Err(e))),
+                    };
+                }
+                ChunkState::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Lines of a response body with their line numbers, split as chunks arrive.
+///
+/// `\n` and `\r\n` end a line, a line longer than the maximum length ends the lines with an error.
+pub(crate) struct Lines {
+    chunks: Chunks,
+    buf: Vec<u8>,
+    // Bytes at the start of `buf` which have no line break.
+    searched: usize,
+    line: usize,
+    max_length: usize,
+    eof: bool,
+    done: bool,
+    // A line is longer than the maximum length.
+    overflow: bool,
+}
+
+impl Lines {
+    pub(crate) fn new(response: ResponseWrapper, max_length: usize) -> Self {
+        Lines {
+            chunks: Chunks::new(response),
+            buf: Vec::new(),
+            searched: 0,
+            line: 0,
+            max_length,
+            eof: false,
+            done: false,
+            overflow: false,
+        }
+    }
+
+    pub(crate) fn set_max_length(&mut self, max_length: usize) {
+        self.max_length = max_length;
+    }
+
+    pub(crate) fn poll_line(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Line>>> {
+        loop {
+            if self.done {
+                return Poll::Ready(None);
+            }
+            let line = match self.buf[self.searched..].iter().position(|b| *b == b'\n') {
+                Some(pos) => {
+                    let mut line: Vec<u8> = self.buf.drain(..=self.searched + pos).collect();
+                    line.pop();
+                    if line.last() == Some(&b'\r') {
+                        line.pop();
+                    }
+                    self.searched = 0;
+                    Some(line)
+                }
+                None if self.eof => {
+                    self.done = true;
+                    Some(std::mem::take(&mut self.buf)).filter(|line| !line.is_empty())
+                }
+                None => {
+                    self.searched = self.buf.len();
+                    None
+                }
+            };
+            let too_long = match &line {
+                Some(line) => line.len() > self.max_length,
+                None => self.buf.len() > self.max_length,
+            };
+            if too_long {
+                self.done = true;
+                self.overflow = true;
+                return Poll::Ready(Some(Err(Error::decode(format!(
+                    "line {} exceeds the maximum length of {} bytes",
+                    self.line + 1,
+                    self.max_length
+                )))));
+            }
+            if let Some(line) = line {
+                self.line += 1;
+                return Poll::Ready(Some(Ok((self.line, line))));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+            match ready!(self.chunks.poll_chunk(cx)) {
+                Some(Ok(chunk)) => self.buf.extend_from_slice(&chunk),
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => self.eof = true,
+            }
+        }
+    }
+}
+
+/// The data of a server-sent event, like `String` or `Json<T>`.
+pub trait EventData: Sized {
+    fn from_data(data: String) -> Result<Self>;
+}
+
+impl EventData for String {
+    fn from_data(data: String) -> Result<Self> {
+        Ok(data)
+    }
+}
+
+impl EventData for Text {
+    fn from_data(data: String) -> Result<Self> {
+        Ok(Text(data))
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T: serde::de::DeserializeOwned> EventData for Json<T> {
+    fn from_data(data: String) -> Result<Self> {
+        serde_json::from_str(&data)
+            .map(Json)
+            .map_err(|e| Error::decode(format!("invalid json in event data: {}", e)))
+    }
+}
+
+/// A server-sent event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event<T = String> {
+    /// The event type, `message` if the event has no `event` field.
+    pub event: String,
+    pub data: T,
+    /// The last event id of the stream when the event is received.
+    pub id: Option<String>,
+    /// The reconnection time in the `retry` field of the event.
+    pub retry: Option<Duration>,
+}
+
+type ReconnectFuture = Pin<Box<dyn Future<Output = Result<ResponseWrapper>> + Send>>;
+
+/// A stream of server-sent events of a `text/event-stream` response, like
+/// `feignhttp::Result<EventStream<Json<Build>>>`.
+///
+/// When the connection is closed, the request is sent again after the retry interval with the
+/// `last-event-id` header. An error of the reconnection ends the stream, so does a `204 No Content`.
+pub struct EventStream<T = String> {
+    lines: Lines,
+    replay: Option<Replay>,
+    reconnecting: Option<ReconnectFuture>,
+    reconnect: bool,
+    retry: Duration,
+    last_event_id: Option<String>,
+    // Fields of the event being received, the id is kept for the next events.
+    id: Option<String>,
+    event: Option<String>,
+    data: String,
+    has_data: bool,
+    event_retry: Option<Duration>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> EventStream<T> {
+    /// The default reconnection time if the server sends no `retry` field.
+    pub const DEFAULT_RETRY: Duration = Duration::from_secs(3);
+
+    pub fn new(mut response: ResponseWrapper) -> Self {
+        let replay = response.take_replay();
+        EventStream {
+            lines: Lines::new(response, DEFAULT_MAX_LINE_LENGTH),
+            replay,
+            reconnecting: None,
+            reconnect: true,
+            retry: Self::DEFAULT_RETRY,
+            last_event_id: None,
+            id: None,
+            event: None,
+            data: String::new(),
+            has_data: false,
+            event_retry: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reconnect when the connection is closed, enabled by default.
+    pub fn reconnect(mut self, reconnect: bool) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Set the maximum length of a line in bytes, a longer line ends the stream with an error.
+    pub fn max_line_length(mut self, max_length: usize) -> Self {
+        self.lines.set_max_length(max_length);
+        self
+    }
+
+    /// The id of the last event, sent by the `last-event-id` header when reconnecting.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// Process a line, an event is returned when a blank line ends it.
+    fn process_line(&mut self, line: &str) -> Option<Event<String>> {
+        if line.is_empty() {
+            self.last_event_id = self.id.clone();
+            let event = self.event.take();
+            let retry = self.event_retry.take();
+            if !std::mem::take(&mut self.has_data) {
+                self.data.clear();
+                return None;
+            }
+            let mut data = std::mem::take(&mut self.data);
+            data.pop();
+            return Some(Event {
+                event: event.unwrap_or_else(|| "message".to_string()),
+                data,
+                id: self.last_event_id.clone(),
+                retry,
+            });
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+                self.has_data = true;
+            }
+            "id" if !value.contains('\0') => {
+                self.id = Some(value.to_string()).filter(|id| !id.is_empty());
+            }
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse() {
+                    self.retry = Duration::from_millis(millis);
+                    self.event_retry = Some(self.retry);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Start reconnecting, `false` if the stream can't reconnect.
+    fn start_reconnect(&mut self) -> bool {
+        let replay = match (&self.replay, self.reconnect) {
+            (Some(replay), true) => replay.clone(),
+            _ => return false,
+        };
+        // An event which is not ended by a blank line is discarded.
+        self.id = self.last_event_id.clone();
+        self.event = None;
+        self.data.clear();
+        self.has_data = false;
+        self.event_retry = None;
+        let headers: Vec<(String, String)> = self
+            .last_event_id
+            .iter()
+            .map(|id| ("last-event-id".to_string(), id.clone()))
+            .collect();
+        let delay = Delay::new(self.retry);
+        self.reconnecting = Some(Box::pin(async move {
+            delay.await;
+            replay.send(headers).await
+        }));
+        true
+    }
+}
+
+impl<T: EventData> Stream for EventStream<T> {
+    type Item = Result<Event<T>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Event<T>>>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(reconnecting) = &mut this.reconnecting {
+                let result = ready!(reconnecting.as_mut().poll(cx));
+                this.reconnecting = None;
+                match result {
+                    Ok(response) if response.status() == http::StatusCode::NO_CONTENT => {
+                        this.replay = None;
+                        return Poll::Ready(None);
+                    }
+                    Ok(response) => {
+                        let max_length = this.lines.max_length;
+                        this.lines = Lines::new(response, max_length);
+                    }
+                    Err(e) => {
+                        this.replay = None;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+            }
+            let line = match ready!(this.lines.poll_line(cx)) {
+                Some(Ok((number, line))) => {
+                    let mut line = String::from_utf8_lossy(&line).into_owned();
+                    if number == 1 && line.starts_with('\u{feff}') {
+                        line.remove(0);
+                    }
+                    line
+                }
+                Some(Err(e)) if this.lines.overflow => {
+                    // A line which is too long ends the stream.
+                    this.replay = None;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Some(Err(e)) => {
+                    if this.start_reconnect() {
+                        continue;
+                    }
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => {
+                    if this.start_reconnect() {
+                        continue;
+                    }
+                    return Poll::Ready(None);
+                }
+            };
+            if let Some(event) = this.process_line(&line) {
+                return Poll::Ready(Some(T::from_data(event.data).map(|data| Event {
+                    event: event.event,
+                    data,
+                    id: event.id,
+                    retry: event.retry,
+                })));
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<T> FromResponse for EventStream<T> {
+    fn accept() -> Option<&'static str> {
+        Some("text/event-stream")
+    }
+
+    async fn from_response(response: ResponseWrapper) -> Result<Self> {
+        Ok(EventStream::new(response))
+    }
+}
+
+/// A timer which doesn't depend on an async runtime, a thread wakes the task when it expires.
+struct Delay {
+    state: Arc<Mutex<(bool, Option<Waker>)>>,
+}
+
+impl Delay {
+    fn new(duration: Duration) -> Self {
+        let state = Arc::new(Mutex::new((false, None::<Waker>)));
+        let timer = state.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(duration);
+            let mut state = timer.lock().unwrap();
+            state.0 = true;
+            if let Some(waker) = state.1.take() {
+                waker.wake();
+            }
+        });
+        Delay { state }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.0 {
+            return Poll::Ready(());
+        }
+        state.1 = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[cfg(feature = "json")]
+pub use self::json::{JsonArray, JsonLines};
+
+#[cfg(feature = "json")]
+mod json {
+    use super::{Chunks, Lines, DEFAULT_MAX_LINE_LENGTH};
+    use crate::error::{Error, Result};
+    use crate::{FromResponse, ResponseWrapper};
+    use async_trait::async_trait;
+    use futures_core::Stream;
+    use serde::de::DeserializeOwned;
+    use std::marker::PhantomData;
+    use std::pin::Pin;
+    use std::task::{ready, Context, Poll};
+
+    /// A stream of newline-delimited JSON values, like `feignhttp::Result<JsonLines<Event>>`.
+    ///
+    /// Each line is decoded as it arrives and blank lines are skipped. A line which fails to decode
+    /// is an error with its line number, and the stream goes on with the next line.
+    pub struct JsonLines<T> {
+        lines: Lines,
+        _marker: PhantomData<fn() -> T>,
+    }
+
+    impl<T> JsonLines<T> {
+        /// The default maximum length of a line in bytes.
+        pub const DEFAULT_MAX_LINE_LENGTH: usize = DEFAULT_MAX_LINE_LENGTH;
+
+        pub fn new(response: ResponseWrapper) -> Self {
+            JsonLines {
+                lines: Lines::new(response, Self::DEFAULT_MAX_LINE_LENGTH),
+                _marker: PhantomData,
+            }
+        }
+
+        /// Set the maximum length of a line in bytes, a longer line ends the stream with an error.
+        pub fn max_line_length(mut self, max_length: usize) -> Self {
+            self.lines.set_max_length(max_length);
+            self
+        }
+    }
+
+    impl<T: DeserializeOwned> Stream for JsonLines<T> {
+        type Item = Result<T>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+            let this = self.get_mut();
+            loop {
+                let (line, bytes) = match ready!(this.lines.poll_line(cx)) {
+                    Some(Ok(line)) => line,
+                    Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                    None => return Poll::Ready(None),
+                };
+                if bytes.iter().all(u8::is_ascii_whitespace) {
+                    continue;
+                }
+                return Poll::Ready(Some(serde_json::from_slice(&bytes).map_err(|e| {
+                    Error::decode(format!("invalid json at line {}: {}", line, e))
+                })));
+            }
+        }
+    }
+
+    #[async_trait]
+    impl<T> FromResponse for JsonLines<T> {
+        fn accept() -> Option<&'static str> {
+            Some("application/x-ndjson, application/jsonl, application/json")
+        }
+
+        async fn from_response(response: ResponseWrapper) -> Result<Self> {
+            Ok(JsonLines::new(response))
+        }
+    }
+
+    /// A stream of the items of a top-level JSON array, like `feignhttp::Result<JsonArray<Report>>`.
+    ///
+    /// Only the item being received is buffered. An item which fails to decode is an error with its
+    /// index, and the stream goes on with the next item. Invalid array syntax ends the stream with an error.
+    pub struct JsonArray<T> {
+        chunks: Chunks,
+        chunk: Vec<u8>,
+        pos: usize,
+        item: Vec<u8>,
+        state: ArrayState,
+        // Nesting depth, and string state of the item being received.
+        depth: usize,
+        in_string: bool,
+        escape: bool,
+        index: usize,
+        max_length: usize,
+        done: bool,
+        _marker: PhantomData<fn() -> T>,
+    }
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum ArrayState {
+        Start,
+        First,
+        Next,
+        Item,
+        End,
+    }
+
+    impl<T> JsonArray<T> {
+        /// The default maximum length of an item in bytes.
+        pub const DEFAULT_MAX_ITEM_LENGTH: usize = DEFAULT_MAX_LINE_LENGTH;
+
+        pub fn new(response: ResponseWrapper) -> Self {
+            JsonArray {
+                chunks: Chunks::new(response),
+                chunk: Vec::new(),
+                pos: 0,
+                item: Vec::new(),
+                state: ArrayState::Start,
+                depth: 0,
+                in_string: false,
+                escape: false,
+                index: 0,
+                max_length: Self::DEFAULT_MAX_ITEM_LENGTH,
+                done: false,
+                _marker: PhantomData,
+            }
+        }
+
+        /// Set the maximum length of an item in bytes, a longer item ends the stream with an error.
+        pub fn max_item_length(mut self, max_length: usize) -> Self {
+            self.max_length = max_length;
+            self
+        }
+
+        /// Scan the received bytes, `true` if an item is complete.
+        fn scan(&mut self) -> Result<bool> {
+            while self.pos < self.chunk.len() {
+                let b = self.chunk[self.pos];
+                self.pos += 1;
+                let state = self.state;
+                match state {
+                    _ if state != ArrayState::Item && b.is_ascii_whitespace() => {}
+                    ArrayState::Start if b == b'[' => self.state = ArrayState::First,
+                    ArrayState::First if b == b']' => self.state = ArrayState::End,
+                    ArrayState::First | ArrayState::Next => {
+                        self.state = ArrayState::Item;
+                        self.pos -= 1;
+                    }
+                    ArrayState::Item => {
+                        if self.in_string {
+                            match b {
+                                _ if self.escape => self.escape = false,
+                                b'\\' => self.escape = true,
+                                b'"' => self.in_string = false,
+                                _ => {}
+                            }
+                        } else {
+                            match b {
+                                b'"' => self.in_string = true,
+                                b'{' | b'[' => self.depth += 1,
+                                b',' | b']' if self.depth == 0 => {
+                                    self.state = if b == b',' { ArrayState::Next } else { ArrayState::End };
+                                    return Ok(true);
+                                }
+                                b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+                                _ => {}
+                            }
+                        }
+                        self.item.push(b);
+                        if self.item.len() > self.max_length {
+                            return Err(Error::decode(format!(
+                                "item {} exceeds the maximum length of {} bytes",
+                                self.index, self.max_length
+                            )));
+                        }
+                    }
+                    ArrayState::Start => return Err(Error::decode("expected a json array")),
+                    ArrayState::End => return Err(Error::decode("unexpected data after json array")),
+                }
+            }
+            Ok(false)
+        }
+    }
+
+    impl<T: DeserializeOwned> Stream for JsonArray<T> {
+        type Item = Result<T>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+            let this = self.get_mut();
+            loop {
+                if this.done {
+                    return Poll::Ready(None);
+                }
+                match this.scan() {
+                    Ok(true) => {
+                        let item = std::mem::take(&mut this.item);
+                        let index = this.index;
+                        this.index += 1;
+                        return Poll::Ready(Some(serde_json::from_slice(&item).map_err(|e| {
+                            Error::decode(format!("invalid json at item {}: {}", index, e))
+                        })));
+                    }
+                    Ok(false) => {}
+                    Err(e) => {
+                        this.done = true;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+                match ready!(this.chunks.poll_chunk(cx)) {
+                    Some(Ok(chunk)) => {
+                        this.chunk = chunk;
+                        this.pos = 0;
+                    }
+                    Some(Err(e)) => {
+                        this.done = true;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    None => {
+                        this.done = true;
+                        if this.state != ArrayState::End {
+                            return Poll::Ready(Some(Err(Error::decode("unexpected end of json array"))));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[async_trait]
+    impl<T> FromResponse for JsonArray<T> {
+        fn accept() -> Option<&'static str> {
+            Some("application/json")
+        }
+
+        async fn from_response(response: ResponseWrapper) -> Result<Self> {
+            Ok(JsonArray::new(response))
+        }
+    }
+}
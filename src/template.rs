@@ -0,0 +1,330 @@
+use crate::error::{Error, Result};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::collections::HashMap;
+
+/// Characters encoded by simple expansion, all but the unreserved characters.
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Characters encoded by reserved expansion, reserved characters and `%` are kept.
+const RESERVED: &AsciiSet = &UNRESERVED
+    .remove(b':')
+    .remove(b'/')
+    .remove(b'?')
+    .remove(b'#')
+    .remove(b'[')
+    .remove(b']')
+    .remove(b'@')
+    .remove(b'!')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b'+')
+    .remove(b',')
+    .remove(b';')
+    .remove(b'=')
+    .remove(b'%');
+
+/// A value of a template variable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateValue {
+    /// A string, it is percent-encoded when expanded.
+    Str(String),
+    /// A string inserted as it is, like the value of `#[path(raw)]`.
+    Raw(String),
+    /// A list of strings, like the value of `#[path] ids: Vec<u32>`.
+    List(Vec<String>),
+}
+
+/// An RFC 6570 URI template, like `/repos/{owner}/{repo}{?page,per_page}`.
+///
+/// Simple (`{var}`), reserved (`{+var}`), fragment (`{#var}`), label (`{.var}`), path segment (`{/var}`),
+/// path parameter (`{;var}`), query (`{?var}`) and query continuation (`{&var}`) expressions are supported,
+/// with prefix (`{var:3}`) and explode (`{list*}`) modifiers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UriTemplate {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Expression(Operator, Vec<VarSpec>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Simple,
+    Reserved,
+    Fragment,
+    Label,
+    Path,
+    PathParam,
+    Query,
+    QueryContinuation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct VarSpec {
+    name: String,
+    prefix: Option<usize>,
+    explode: bool,
+}
+
+impl Operator {
+    fn from_char(c: char) -> Option<Operator> {
+        match c {
+            '+' => Some(Operator::Reserved),
+            '#' => Some(Operator::Fragment),
+            '.' => Some(Operator::Label),
+            '/' => Some(Operator::Path),
+            ';' => Some(Operator::PathParam),
+            '?' => Some(Operator::Query),
+            '&' => Some(Operator::QueryContinuation),
+            _ => None,
+        }
+    }
+
+    /// The first string, separator, named and if-empty string of the expansion.
+    fn rules(self) -> (&'static str, &'static str, bool, &'static str) {
+        match self {
+            Operator::Simple | Operator::Reserved => ("", ",", false, ""),
+            Operator::Fragment => ("#", ",", false, ""),
+            Operator::Label => (".", ".", false, ""),
+            Operator::Path => ("/", "/", false, ""),
+            Operator::PathParam => (";", ";", true, ""),
+            Operator::Query => ("?", "&", true, "="),
+            Operator::QueryContinuation => ("&", "&", true, "="),
+        }
+    }
+
+    fn allow_reserved(self) -> bool {
+        matches!(self, Operator::Reserved | Operator::Fragment)
+    }
+
+    /// Variables of simple and reserved expressions are required, others are skipped if undefined.
+    fn is_required(self) -> bool {
+        matches!(self, Operator::Simple | Operator::Reserved)
+    }
+}
+
+impl UriTemplate {
+    /// Parse a template, braces which are not a valid expression are kept as literals.
+    pub fn parse(template: &str) -> UriTemplate {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            literal.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let expression = rest
+                .find('}')
+                .and_then(|end| parse_expression(&rest[1..end]).map(|e| (e, end)));
+            match expression {
+                Some(((operator, vars), end)) => {
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Expression(operator, vars));
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    literal.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        UriTemplate { parts }
+    }
+
+    /// Names of the variables in the template.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        for part in &self.parts {
+            if let Part::Expression(_, vars) = part {
+                names.extend(vars.iter().map(|var| var.name.as_str()));
+            }
+        }
+        names
+    }
+
+    /// Expand the template with path values, and param values if a path value is not found.
+    ///
+    /// A build error is returned if a variable of a simple or reserved expression is undefined,
+    /// or a path value is a `.` or `..` segment.
+    pub fn expand(
+        &self,
+        paths: &HashMap<&str, TemplateValue>,
+        params: &HashMap<&str, String>,
+    ) -> Result<String> {
+        let mut out = String::new();
+        for part in &self.parts {
+            let (operator, vars) = match part {
+                Part::Literal(literal) => {
+                    out.push_str(literal);
+                    continue;
+                }
+                Part::Expression(operator, vars) => (*operator, vars),
+            };
+            let (first, sep, named, if_empty) = operator.rules();
+            let mut expanded = Vec::new();
+            for var in vars {
+                let param;
+                let value = match (paths.get(var.name.as_str()), params.get(var.name.as_str())) {
+                    (Some(value), _) => value,
+                    (None, Some(value)) => {
+                        param = TemplateValue::Str(value.clone());
+                        &param
+                    }
+                    (None, None) if operator.is_required() => {
+                        return Err(Error::build(format!(
+                            "unresolved placeholder {{{}}} in url",
+                            var.name
+                        )))
+                    }
+                    (None, None) => continue,
+                };
+                if let Some(value) = expand_var(operator, var, value, sep, named, if_empty) {
+                    expanded.push(value);
+                }
+            }
+            if expanded.is_empty() {
+                continue;
+            }
+            let expanded = expanded.join(sep);
+            check_dot_segment(operator, &out, &expanded, part, &self.parts)?;
+            out.push_str(first);
+            out.push_str(&expanded);
+        }
+        Ok(out)
+    }
+}
+
+fn parse_expression(expression: &str) -> Option<(Operator, Vec<VarSpec>)> {
+    let mut chars = expression.chars();
+    let (operator, list) = match chars.next().and_then(Operator::from_char) {
+        Some(operator) => (operator, chars.as_str()),
+        None => (Operator::Simple, expression),
+    };
+    let mut vars = Vec::new();
+    for spec in list.split(',') {
+        let (name, prefix, explode) = match spec.split_once(':') {
+            Some((name, len)) => (
+                name,
+                Some(len.parse::<usize>().ok().filter(|l| *l > 0)?),
+                false,
+            ),
+            None => match spec.strip_suffix('*') {
+                Some(name) => (name, None, true),
+                None => (spec, None, false),
+            },
+        };
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+        if !valid {
+            return None;
+        }
+        vars.push(VarSpec {
+            name: name.to_string(),
+            prefix,
+            explode,
+        });
+    }
+    Some((operator, vars))
+}
+
+fn expand_var(
+    operator: Operator,
+    var: &VarSpec,
+    value: &TemplateValue,
+    sep: &str,
+    named: bool,
+    if_empty: &str,
+) -> Option<String> {
+    let encode = |s: &str| -> String {
+        match value {
+            TemplateValue::Raw(_) => s.to_string(),
+            _ if operator.allow_reserved() => utf8_percent_encode(s, RESERVED).to_string(),
+            _ => utf8_percent_encode(s, UNRESERVED).to_string(),
+        }
+    };
+    let named_value = |s: String| -> String {
+        if !named {
+            s
+        } else if s.is_empty() {
+            format!("{}{}", var.name, if_empty)
+        } else {
+            format!("{}={}", var.name, s)
+        }
+    };
+    match value {
+        TemplateValue::Str(s) | TemplateValue::Raw(s) => {
+            let s = match var.prefix {
+                Some(len) => s.chars().take(len).collect(),
+                None => s.clone(),
+            };
+            Some(named_value(encode(&s)))
+        }
+        TemplateValue::List(items) if items.is_empty() => None,
+        TemplateValue::List(items) if var.explode => Some(
+            items
+                .iter()
+                .map(|item| named_value(encode(item)))
+                .collect::<Vec<_>>()
+                .join(sep),
+        ),
+        TemplateValue::List(items) => Some(named_value(
+            items
+                .iter()
+                .map(|item| encode(item))
+                .collect::<Vec<_>>()
+                .join(","),
+        )),
+    }
+}
+
+/// Reject `.` and `..` path segments, which change the request target after normalization.
+fn check_dot_segment(
+    operator: Operator,
+    before: &str,
+    expanded: &str,
+    part: &Part,
+    parts: &[Part],
+) -> Result<()> {
+    let is_dot = |s: &str| s == "." || s == "..";
+    let invalid = match operator {
+        Operator::Path => expanded.split('/').any(is_dot),
+        Operator::Simple | Operator::Reserved if is_dot(expanded) => {
+            let after = parts.iter().skip_while(|p| !std::ptr::eq(*p, part)).nth(1);
+            let segment_start = before.is_empty() || before.ends_with('/');
+            let segment_end = match after {
+                Some(Part::Literal(literal)) => literal.starts_with(['/', '?', '#']),
+                Some(Part::Expression(op, _)) => {
+                    !matches!(op, Operator::Simple | Operator::Reserved | Operator::Label)
+                }
+                None => true,
+            };
+            segment_start && segment_end
+        }
+        _ => false,
+    };
+    if invalid {
+        return Err(Error::build(format!(
+            "invalid path segment {} in url",
+            expanded
+        )));
+    }
+    Ok(())
+}
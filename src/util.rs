@@ -1,9 +1,306 @@
+use crate::{ClientConfig, Decoder, Encoder, Error, FromResponse, HttpResponse, IntoBody, ResponseWrapper, Result};
+use async_trait::async_trait;
 use std::collections::HashMap;
+use std::marker::PhantomData;
+
+pub use crate::page::{Cursor, Page};
+pub use once_cell::sync::Lazy;
+pub use serde;
 
 pub fn replace(placeholder: &str, map: &HashMap<&str, String>) -> String {
     let mut placeholder = placeholder.to_string();
     for (k, v) in map {
-        placeholder = placeholder.replace(format!("{{{}}}", k).as_str(), &v);
+        placeholder = placeholder.replace(format!("{{{}}}", k).as_str(), v);
     }
     placeholder
 }
+
+/// Resolve `${NAME}` and `${NAME:default}` placeholders with environment variables.
+///
+/// A placeholder without default requires the variable to be set, otherwise a config error is returned.
+pub fn resolve_env(s: &str) -> Result<String> {
+    let mut resolved = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        resolved.push_str(&rest[..start]);
+        let placeholder = &rest[start + 2..];
+        let end = placeholder
+            .find('}')
+            .ok_or_else(|| Error::config(format!("unclosed placeholder in {}", s)))?;
+        let (name, default) = match placeholder[..end].split_once(':') {
+            Some((name, default)) => (name.trim(), Some(default)),
+            None => (placeholder[..end].trim(), None),
+        };
+        match (std::env::var(name), default) {
+            (Ok(value), _) => resolved.push_str(&value),
+            (Err(_), Some(default)) => resolved.push_str(default),
+            (Err(_), None) => {
+                return Err(Error::config(format!(
+                    "environment variable {} not found",
+                    name
+                )))
+            }
+        }
+        rest = &placeholder[end + 1..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// Override a value of the profile with the attribute metadata.
+pub fn set_config(client_config: &mut ClientConfig, key: &str, value: String) -> Result<()> {
+    client_config.set(key, value)
+}
+
+/// Get the url in the attribute, or the base url of the profile.
+pub fn base_url(client_config: &ClientConfig, url: Option<String>) -> Result<String> {
+    url.or_else(|| client_config.base_url.clone())
+        .ok_or_else(|| Error::config("no url specified in the attribute or the profile"))
+}
+
+/// Join a path to a base url, like `http://xxx/api/v2/` and `/users?page=1`.
+///
+/// The base path prefix is kept and slashes between them are normalized. Query strings of both are merged,
+/// the base query goes first and a query expression like `{?page}` becomes a continuation like `{&page}`.
+/// A fragment of the path replaces the fragment of the base.
+pub fn join_url(base: &str, path: &str) -> String {
+    if path.is_empty() {
+        return base.to_string();
+    }
+    let (base, base_fragment) = split_outside(base, '#');
+    let (base_path, base_query) = split_outside(base, '?');
+    let (path, path_fragment) = split_outside(path, '#');
+    let (path, path_query) = split_outside(path, '?');
+    // Query expressions of the path template follow the literal query strings.
+    let (path, query_template) = match path.find("{?").or_else(|| path.find("{&")) {
+        Some(i) => (&path[..i], &path[i..]),
+        None => (path, ""),
+    };
+
+    let mut url = base_path.to_string();
+    match (url.ends_with('/'), path.starts_with('/')) {
+        (true, true) => url.push_str(&path[1..]),
+        (false, false) if starts_segment(path) => {
+            url.push('/');
+            url.push_str(path);
+        }
+        _ => url.push_str(path),
+    }
+
+    let query: Vec<&str> = base_query
+        .into_iter()
+        .chain(path_query)
+        .filter(|q| !q.is_empty())
+        .collect();
+    if !query.is_empty() {
+        url.push('?');
+        url.push_str(&query.join("&"));
+        url.push_str(&query_template.replacen("{?", "{&", 1));
+    } else {
+        url.push_str(query_template);
+    }
+
+    if let Some(fragment) = path_fragment.or(base_fragment) {
+        url.push('#');
+        url.push_str(fragment);
+    }
+    url
+}
+
+/// Split at the first separator outside template expressions like `{?page}`.
+fn split_outside(s: &str, separator: char) -> (&str, Option<&str>) {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            c if c == separator && depth == 0 => return (&s[..i], Some(&s[i + 1..])),
+            _ => {}
+        }
+    }
+    (s, None)
+}
+
+/// A path starting with a segment like `users` or `{id}` needs a slash after the base path.
+fn starts_segment(path: &str) -> bool {
+    let mut chars = path.chars();
+    match chars.next() {
+        Some('{') => !matches!(chars.next(), Some('+' | '#' | '.' | '/' | ';' | '?' | '&')),
+        Some(c) => c.is_alphanumeric() || matches!(c, '-' | '_' | '~' | '%'),
+        None => false,
+    }
+}
+
+/// Replace `{name}` placeholders of a body template with param values, escaped for the content type.
+///
+/// Values are escaped as JSON string content for JSON, form encoded for forms and inserted as they are otherwise.
+/// A build error is returned if a placeholder has no value.
+pub fn render_body(template: &str, map: &HashMap<&str, String>, content_type: &str) -> Result<String> {
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_lowercase();
+    let escape: fn(&str) -> String = if mime == "application/json" || mime.ends_with("+json") {
+        escape_json
+    } else if mime == "application/x-www-form-urlencoded" {
+        |s| url::form_urlencoded::byte_serialize(s.as_bytes()).collect()
+    } else {
+        str::to_string
+    };
+
+    let mut body = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        body.push_str(&rest[..start]);
+        let placeholder = &rest[start + 1..];
+        let end = placeholder
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(placeholder.len());
+        if end == 0 || !placeholder[end..].starts_with('}') {
+            body.push('{');
+            rest = placeholder;
+            continue;
+        }
+        let name = &placeholder[..end];
+        let value = map
+            .get(name)
+            .ok_or_else(|| Error::build(format!("unresolved placeholder {{{}}} in body", name)))?;
+        body.push_str(&escape(value));
+        rest = &placeholder[end + 1..];
+    }
+    body.push_str(rest);
+    Ok(body)
+}
+
+/// Escape a value as the content of a JSON string.
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Select the body of a `#[body]` argument, `IntoBody` if it is implemented, or the codec otherwise.
+pub struct Encode<T, C>(pub T, pub C);
+
+pub trait EncodeBody {
+    type Body: IntoBody;
+
+    fn body(self) -> Self::Body;
+}
+
+impl<T: IntoBody, C> EncodeBody for Encode<T, C> {
+    type Body = T;
+
+    fn body(self) -> T {
+        self.0
+    }
+}
+
+pub trait EncodeCodec {
+    type Body: IntoBody;
+
+    fn body(self) -> Self::Body;
+}
+
+impl<'a, T: serde::Serialize, C: Encoder> EncodeCodec for &'a Encode<T, C> {
+    type Body = Encoded<'a, T, C>;
+
+    fn body(self) -> Self::Body {
+        Encoded(&self.0, &self.1)
+    }
+}
+
+/// A value serialized by a codec when it is sent.
+pub struct Encoded<'a, T, C>(&'a T, &'a C);
+
+impl<T: serde::Serialize, C: Encoder> IntoBody for Encoded<'_, T, C> {
+    fn content_type(&self) -> &str {
+        self.1.content_type()
+    }
+
+    fn into_body(self) -> Result<Vec<u8>> {
+        self.1.encode(self.0)
+    }
+}
+
+/// Select the decoding of a return type, `FromResponse` if it is implemented, or the codec otherwise.
+pub struct Decode<T, C>(C, PhantomData<fn() -> T>);
+
+impl<T, C> Decode<T, C> {
+    pub fn new(codec: C) -> Self {
+        Decode(codec, PhantomData)
+    }
+}
+
+#[async_trait]
+pub trait DecodeResponse<T> {
+    fn accept(self) -> Option<&'static str>;
+
+    async fn decode(self, response: ResponseWrapper) -> Result<T>;
+}
+
+#[async_trait]
+impl<T: FromResponse, C: Send> DecodeResponse<T> for Decode<T, C> {
+    fn accept(self) -> Option<&'static str> {
+        T::accept()
+    }
+
+    async fn decode(self, response: ResponseWrapper) -> Result<T> {
+        T::from_response(response).await
+    }
+}
+
+#[async_trait]
+pub trait DecodeCodec<T> {
+    fn accept(self) -> Option<&'static str>;
+
+    async fn decode(self, response: ResponseWrapper) -> Result<T>;
+}
+
+#[async_trait]
+impl<T: serde::de::DeserializeOwned, C: Decoder + Sync> DecodeCodec<T> for &Decode<T, C> {
+    fn accept(self) -> Option<&'static str> {
+        Some(self.0.accept())
+    }
+
+    async fn decode(self, response: ResponseWrapper) -> Result<T> {
+        let body = response.vec().await?;
+        self.0.decode(&body)
+    }
+}
+
+/// Check the response `content-type` against the expected media types, like `application/json, text/*`.
+///
+/// A response without `content-type` is accepted, a decode error is returned if no media type matches.
+pub fn check_content_type(headers: &http::HeaderMap, expected: &str) -> Result<()> {
+    let actual = match headers.get(http::header::CONTENT_TYPE).and_then(|v| v.to_str().ok()) {
+        Some(actual) => actual,
+        None => return Ok(()),
+    };
+    let essence = |s: &str| s.split(';').next().unwrap_or_default().trim().to_lowercase();
+    let actual_type = essence(actual);
+    let matched = expected.split(',').map(essence).any(|range| {
+        let (range_type, range_subtype) = range.split_once('/').unwrap_or((&range, ""));
+        let (actual_main, actual_subtype) = actual_type.split_once('/').unwrap_or((&actual_type, ""));
+        range == "*/*"
+            || range == actual_type
+            || (range_subtype == "*" && range_type == actual_main)
+            // A structured syntax suffix like `application/vnd.github+json` matches `application/json`.
+            || (range_type == actual_main && actual_subtype.ends_with(&format!("+{}", range_subtype)))
+    });
+    if !matched {
+        return Err(Error::decode(format!(
+            "unexpected content type {}, expected {}",
+            actual, expected
+        )));
+    }
+    Ok(())
+}
+
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use feignhttp::{post, Bytes, FromResponse, HttpResponse, IntoBody, ResponseWrapper, Text};
+
+use mockito::mock;
+
+type Message = String;
+
+#[post("http://localhost:1234/body_text")]
+async fn post_text(#[body(text)] message: Message) -> feignhttp::Result<Text> {}
+
+#[post("http://localhost:1234/body_box")]
+async fn post_box(#[body] message: Box<str>) -> feignhttp::Result<Box<str>> {}
+
+#[post("http://localhost:1234/body_bytes")]
+async fn post_bytes(#[body] data: Bytes) -> feignhttp::Result<Bytes> {}
+
+#[post("http://localhost:1234/body_slice")]
+async fn post_slice(#[body(bytes)] data: &[u8]) -> feignhttp::Result<Vec<u8>> {}
+
+#[tokio::test]
+async fn test_text_and_bytes() {
+    let _mock_text = mock("POST", "/body_text")
+        .match_header("content-type", "text/plain")
+        .match_body("hello")
+        .with_body("world")
+        .create();
+    let _mock_box = mock("POST", "/body_box")
+        .match_header("content-type", "text/plain")
+        .match_body("hello")
+        .with_body("world")
+        .create();
+    let _mock_bytes = mock("POST", "/body_bytes")
+        .match_header("content-type", "application/octet-stream")
+        .match_body("aaa")
+        .with_body("bbb")
+        .create();
+    let _mock_slice = mock("POST", "/body_slice")
+        .match_header("content-type", "application/octet-stream")
+        .match_body("aaa")
+        .with_body("bbb")
+        .create();
+
+    assert_eq!(post_text("hello".to_string()).await.unwrap(), Text("world".to_string()));
+    assert_eq!(&*post_box("hello".into()).await.unwrap(), "world");
+    assert_eq!(post_bytes(Bytes(b"aaa".to_vec())).await.unwrap().into_inner(), b"bbb");
+    assert_eq!(post_slice(b"aaa").await.unwrap(), b"bbb");
+}
+
+struct Csv(Vec<Vec<String>>);
+
+impl IntoBody for Csv {
+    fn content_type(&self) -> &str {
+        "text/csv"
+    }
+
+    fn into_body(self) -> feignhttp::Result<Vec<u8>> {
+        let lines: Vec<String> = self.0.iter().map(|row| row.join(",")).collect();
+        Ok(lines.join("\n").into_bytes())
+    }
+}
+
+#[async_trait]
+impl FromResponse for Csv {
+    async fn from_response(response: ResponseWrapper) -> feignhttp::Result<Self> {
+        let text = response.text().await?;
+        Ok(Csv(text
+            .lines()
+            .map(|line| line.split(',').map(String::from).collect())
+            .collect()))
+    }
+}
+
+#[post("http://localhost:1234/body_csv")]
+async fn post_csv(#[body] csv: Csv) -> feignhttp::Result<Csv> {}
+
+#[tokio::test]
+async fn test_custom_body() {
+    let _mock = mock("POST", "/body_csv")
+        .match_header("content-type", "text/csv")
+        .match_body("a,b\n1,2")
+        .with_body("c,d")
+        .create();
+
+    let csv = Csv(vec![
+        vec!["a".to_string(), "b".to_string()],
+        vec!["1".to_string(), "2".to_string()],
+    ]);
+    let r = post_csv(csv).await.unwrap();
+    assert_eq!(r.0, vec![vec!["c".to_string(), "d".to_string()]]);
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use super::*;
+    use feignhttp::Json;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    pub struct User {
+        pub id: i32,
+    }
+
+    #[post("http://localhost:1234/body_json")]
+    pub async fn post_json(#[body] user: Json<User>) -> feignhttp::Result<Json<User>> {}
+
+    #[post("http://localhost:1234/body_json_string")]
+    pub async fn post_json_string(#[body(json)] name: &str) -> feignhttp::Result<User> {}
+}
+
+#[tokio::test]
+async fn test_json() {
+    #[cfg(feature = "json")]
+    {
+        use feignhttp::Json;
+        use json::*;
+
+        let _mock = mock("POST", "/body_json")
+            .match_header("content-type", "application/json")
+            .match_body(r#"{"id":1}"#)
+            .with_body(r#"{"id":2}"#)
+            .create();
+        let _mock_string = mock("POST", "/body_json_string")
+            .match_header("content-type", "application/json")
+            .match_body(r#""jack""#)
+            .with_body(r#"{"id":3}"#)
+            .create();
+
+        assert_eq!(post_json(Json(User { id: 1 })).await.unwrap().id, 2);
+        assert_eq!(post_json_string("jack").await.unwrap(), User { id: 3 });
+    }
+}
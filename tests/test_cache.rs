@@ -0,0 +1,150 @@
+use feignhttp::{feign, get, Feign};
+use mockito::{mock, Matcher};
+
+#[get("http://localhost:1234/cache/{name}", cache = "memory")]
+async fn cached(#[path] name: &str) -> feignhttp::Result<String> {}
+
+#[get("http://localhost:1234/cache/{name}", cache = "memory")]
+async fn cached_with(#[path] name: &str, #[header] accept: &str) -> feignhttp::Result<String> {}
+
+#[get("http://localhost:1234/cache/{name}", cache = "disk:target/test_cache")]
+async fn cached_on_disk(#[path] name: &str) -> feignhttp::Result<String> {}
+
+#[derive(Feign)]
+struct Settings;
+
+#[feign(url = "http://localhost:1234/cache", cache = "memory")]
+impl Settings {
+    #[get("/settings")]
+    async fn get(&self) -> feignhttp::Result<String> {}
+
+    #[get("/settings", headers = "cache-control: no-cache")]
+    async fn get_latest(&self) -> feignhttp::Result<String> {}
+}
+
+#[tokio::test]
+async fn test_cache_fresh() {
+    let mock_fresh = mock("GET", "/cache/fresh")
+        .with_header("cache-control", "max-age=60")
+        .with_body("v1")
+        .expect(1)
+        .create();
+    let mock_expires = mock("GET", "/cache/expires")
+        .with_header("expires", "Wed, 21 Oct 2099 07:28:00 GMT")
+        .with_body("v1")
+        .expect(1)
+        .create();
+    let mock_expired = mock("GET", "/cache/expired")
+        .with_header("cache-control", "max-age=60")
+        .with_header("age", "120")
+        .with_body("v1")
+        .expect(2)
+        .create();
+
+    // A fresh response is not requested again.
+    assert_eq!(cached("fresh").await.unwrap(), "v1");
+    assert_eq!(cached("fresh").await.unwrap(), "v1");
+    mock_fresh.assert();
+
+    assert_eq!(cached("expires").await.unwrap(), "v1");
+    assert_eq!(cached("expires").await.unwrap(), "v1");
+    mock_expires.assert();
+
+    assert_eq!(cached("expired").await.unwrap(), "v1");
+    assert_eq!(cached("expired").await.unwrap(), "v1");
+    mock_expired.assert();
+}
+
+#[tokio::test]
+async fn test_cache_revalidate() {
+    let _mock = mock("GET", "/cache/etag")
+        .match_header("if-none-match", Matcher::Missing)
+        .with_header("cache-control", "no-cache")
+        .with_header("etag", "\"v1\"")
+        .with_body("v1")
+        .expect(1)
+        .create();
+    let mock_not_modified = mock("GET", "/cache/etag")
+        .match_header("if-none-match", "\"v1\"")
+        .with_status(304)
+        .expect(2)
+        .create();
+    let _mock_modified = mock("GET", "/cache/modified")
+        .match_header("if-modified-since", Matcher::Missing)
+        .with_header("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT")
+        .with_body("v1")
+        .create();
+    let mock_modified_since = mock("GET", "/cache/modified")
+        .match_header("if-modified-since", "Wed, 21 Oct 2015 07:28:00 GMT")
+        .with_header("last-modified", "Wed, 21 Oct 2020 07:28:00 GMT")
+        .with_body("v2")
+        .expect(1)
+        .create();
+
+    // The cached body is returned for a 304 response.
+    assert_eq!(cached("etag").await.unwrap(), "v1");
+    assert_eq!(cached("etag").await.unwrap(), "v1");
+    assert_eq!(cached("etag").await.unwrap(), "v1");
+    mock_not_modified.assert();
+
+    assert_eq!(cached("modified").await.unwrap(), "v1");
+    assert_eq!(cached("modified").await.unwrap(), "v2");
+    mock_modified_since.assert();
+}
+
+#[tokio::test]
+async fn test_cache_vary() {
+    let mock_vary = mock("GET", "/cache/vary")
+        .with_header("cache-control", "max-age=60")
+        .with_header("vary", "Accept")
+        .with_body("v1")
+        .expect(2)
+        .create();
+    let mock_no_store = mock("GET", "/cache/no_store")
+        .with_header("cache-control", "no-store, max-age=60")
+        .with_body("v1")
+        .expect(2)
+        .create();
+
+    // A request with other values of the headers named by `vary` is not served by the cache.
+    assert_eq!(cached_with("vary", "text/plain").await.unwrap(), "v1");
+    assert_eq!(cached_with("vary", "text/plain").await.unwrap(), "v1");
+    assert_eq!(cached_with("vary", "text/html").await.unwrap(), "v1");
+    mock_vary.assert();
+
+    assert_eq!(cached("no_store").await.unwrap(), "v1");
+    assert_eq!(cached("no_store").await.unwrap(), "v1");
+    mock_no_store.assert();
+}
+
+#[tokio::test]
+async fn test_cache_request_no_cache() {
+    let mock_settings = mock("GET", "/cache/settings")
+        .with_header("cache-control", "max-age=60")
+        .with_header("etag", "\"v1\"")
+        .with_body("v1")
+        .expect(2)
+        .create();
+
+    assert_eq!(Settings.get().await.unwrap(), "v1");
+    assert_eq!(Settings.get().await.unwrap(), "v1");
+    // `cache-control: no-cache` of the request revalidates the cached response.
+    assert_eq!(Settings.get_latest().await.unwrap(), "v1");
+    mock_settings.assert();
+}
+
+#[tokio::test]
+async fn test_cache_disk() {
+    let _ = std::fs::remove_dir_all("target/test_cache");
+    let mock_disk = mock("GET", "/cache/disk")
+        .with_header("cache-control", "max-age=60")
+        .with_header("content-type", "text/plain")
+        .with_body("on disk")
+        .expect(1)
+        .create();
+
+    assert_eq!(cached_on_disk("disk").await.unwrap(), "on disk");
+    assert_eq!(cached_on_disk("disk").await.unwrap(), "on disk");
+    assert_eq!(std::fs::read_dir("target/test_cache").unwrap().count(), 1);
+    mock_disk.assert();
+}
@@ -0,0 +1,227 @@
+#![allow(unused_imports)]
+
+use feignhttp::{feign, get, load_config, parse_duration, register_profile, ClientConfig, Feign};
+
+use mockito::mock;
+use std::time::Duration;
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("3000").unwrap(), Duration::from_millis(3000));
+    assert_eq!(parse_duration("300ms").unwrap(), Duration::from_millis(300));
+    assert_eq!(parse_duration("5s").unwrap(), Duration::from_secs(5));
+    assert_eq!(parse_duration("1m30s").unwrap(), Duration::from_secs(90));
+    assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+    assert!(parse_duration("abc").unwrap_err().is_config_error());
+    assert!(parse_duration("5d").unwrap_err().is_config_error());
+    assert!(parse_duration("s").unwrap_err().is_config_error());
+}
+
+#[test]
+fn test_from_env() {
+    std::env::set_var("ENV_TEST__BASE_URL", "http://localhost:1234");
+    std::env::set_var("ENV_TEST__TIMEOUT", "5s");
+    std::env::set_var("ENV_TEST__HEADERS__X_API_KEY", "MTIzNDU2");
+    std::env::set_var("ENV_TEST__CACHE", "disk:/tmp/feignhttp");
+
+    let config = ClientConfig::from_env("env_test").unwrap();
+    assert_eq!(config.base_url.as_deref(), Some("http://localhost:1234"));
+    assert_eq!(config.timeout, Some(Duration::from_secs(5)));
+    assert_eq!(config.connect_timeout, None);
+    assert_eq!(config.cache.as_deref(), Some("disk:/tmp/feignhttp"));
+    assert_eq!(config.headers.get("x-api-key").map(|v| v.as_str()), Some("MTIzNDU2"));
+}
+
+#[test]
+fn test_from_env_error() {
+    std::env::set_var("ENV_ERROR__TIMEOUT", "five seconds");
+    assert!(ClientConfig::from_env("env_error").unwrap_err().is_config_error());
+
+    std::env::set_var("ENV_UNKNOWN__TIMEOUTS", "5s");
+    assert!(ClientConfig::from_env("env_unknown").unwrap_err().is_config_error());
+
+    std::env::set_var("ENV_CACHE__CACHE", "redis");
+    assert!(ClientConfig::from_env("env_cache").unwrap_err().is_config_error());
+}
+
+#[test]
+fn test_load_config() {
+    #[cfg(feature = "toml")]
+    {
+        let path = std::env::temp_dir().join("feignhttp_test_load_config.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [file_test]
+            base_url = "http://localhost:1234"
+            connect_timeout = 3000
+            timeout = "10s"
+
+            [file_test.headers]
+            accept = "application/json"
+            "#,
+        )
+        .unwrap();
+        load_config(&path).unwrap();
+
+        // Environment variables override the file.
+        std::env::set_var("FILE_TEST__TIMEOUT", "1s");
+        let config = ClientConfig::named("file_test").unwrap();
+        assert_eq!(config.base_url.as_deref(), Some("http://localhost:1234"));
+        assert_eq!(config.connect_timeout, Some(Duration::from_millis(3000)));
+        assert_eq!(config.timeout, Some(Duration::from_secs(1)));
+        assert_eq!(config.headers.get("accept").map(|v| v.as_str()), Some("application/json"));
+    }
+}
+
+#[test]
+fn test_load_config_error() {
+    #[cfg(feature = "toml")]
+    {
+        let path = std::env::temp_dir().join("feignhttp_test_load_config_error.toml");
+        std::fs::write(&path, "[bad]\ntimeout = \"abc\"\n").unwrap();
+        assert!(load_config(&path).unwrap_err().is_config_error());
+
+        std::fs::write(&path, "[bad]\nbase = \"http://localhost\"\n").unwrap();
+        assert!(load_config(&path).unwrap_err().is_config_error());
+    }
+
+    let path = std::env::temp_dir().join("feignhttp_test_load_config_error.ini");
+    std::fs::write(&path, "").unwrap();
+    assert!(load_config(&path).unwrap_err().is_config_error());
+}
+
+#[get(config = "fn_config", path = "/fn_config")]
+async fn fn_config() -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_fn_config() {
+    let _mock = mock("GET", "/fn_config")
+        .match_header("accept", "application/json")
+        .create();
+
+    std::env::set_var("FN_CONFIG__BASE_URL", "http://localhost:1234");
+    std::env::set_var("FN_CONFIG__HEADERS__ACCEPT", "application/json");
+    fn_config().await.unwrap();
+}
+
+#[get(config = "missing_config", path = "/missing_config")]
+async fn missing_config() -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_missing_config() {
+    assert!(missing_config().await.unwrap_err().is_config_error());
+}
+
+#[derive(Feign)]
+struct Client;
+
+#[feign(config = "struct_config")]
+impl Client {
+    #[get("/struct_config", headers = "accept: text/plain")]
+    async fn home(&self) -> feignhttp::Result<String> {}
+}
+
+#[tokio::test]
+async fn test_struct_config() {
+    let _mock = mock("GET", "/struct_config")
+        .match_header("accept", "text/plain")
+        .match_header("token", "MTIzNDU2")
+        .create();
+
+    std::env::set_var("STRUCT_CONFIG__BASE_URL", "http://localhost:1234");
+    std::env::set_var("STRUCT_CONFIG__HEADERS__ACCEPT", "application/json");
+    std::env::set_var("STRUCT_CONFIG__HEADERS__TOKEN", "MTIzNDU2");
+    Client.home().await.unwrap();
+}
+
+#[get(
+    url = "${ENV_URL:http://localhost:1234}",
+    path = "/env/{id}",
+    headers = "token: ${ENV_TOKEN}; accept: ${ENV_ACCEPT:text/plain}",
+    timeout = "${ENV_TIMEOUT:3s}"
+)]
+async fn env_placeholder(#[path] id: i32) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_env_placeholder() {
+    let _mock = mock("GET", "/env/1")
+        .match_header("token", "MTIzNDU2")
+        .match_header("accept", "text/plain")
+        .create();
+
+    std::env::set_var("ENV_TOKEN", "MTIzNDU2");
+    env_placeholder(1).await.unwrap();
+}
+
+#[get("http://localhost:1234/env_missing", headers = "token: ${ENV_MISSING_TOKEN}")]
+async fn env_missing() -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_env_missing() {
+    assert!(env_missing().await.unwrap_err().is_config_error());
+}
+
+#[derive(Feign)]
+struct Billing;
+
+#[feign(profile = "billing", path = "/billing")]
+impl Billing {
+    #[get("/invoices", headers = "accept: text/plain")]
+    async fn invoices(&self) -> feignhttp::Result<String> {}
+
+    #[get(path = "/invoices", profile = "billing_slow", timeout = 1000)]
+    async fn other_invoices(&self) -> feignhttp::Result<String> {}
+}
+
+#[tokio::test]
+async fn test_profile() {
+    let _mock = mock("GET", "/billing/invoices")
+        .match_header("accept", "text/plain")
+        .match_header("x-api-key", "MTIzNDU2")
+        .create();
+    let _mock_other = mock("GET", "/other/billing/invoices")
+        .match_header("accept", "application/json")
+        .create();
+
+    let mut headers = std::collections::HashMap::new();
+    headers.insert("accept".to_string(), "application/json".to_string());
+    headers.insert("x-api-key".to_string(), "MTIzNDU2".to_string());
+    register_profile(
+        "billing",
+        ClientConfig {
+            base_url: Some("http://localhost:1234".to_string()),
+            timeout: Some(Duration::from_secs(5)),
+            headers: headers.clone(),
+            ..Default::default()
+        },
+    );
+    register_profile(
+        "billing_slow",
+        ClientConfig {
+            base_url: Some("http://localhost:1234/other".to_string()),
+            timeout: Some(Duration::from_secs(60)),
+            headers,
+            ..Default::default()
+        },
+    );
+
+    // Metadata in the attribute overrides the profile.
+    Billing.invoices().await.unwrap();
+    Billing.other_invoices().await.unwrap();
+}
+
+#[test]
+fn test_profile_override_env() {
+    register_profile(
+        "profile_env",
+        ClientConfig {
+            base_url: Some("http://localhost:1".to_string()),
+            ..Default::default()
+        },
+    );
+    std::env::set_var("PROFILE_ENV__BASE_URL", "http://localhost:1234");
+
+    let config = ClientConfig::named("profile_env").unwrap();
+    assert_eq!(config.base_url.as_deref(), Some("http://localhost:1234"));
+}
@@ -60,9 +60,7 @@ async fn test_query() {
         ("name", "xxx".to_string()),
         ("name", "xxx2".to_string()),
     ]
-    .iter()
-    .cloned()
-    .collect();
+    .to_vec();
 
     let request = HttpClient::builder()
         .url(&url)
@@ -88,9 +86,7 @@ async fn test_send_form() {
         ("name", "xxx".to_string()),
         ("name", "xxx2".to_string()),
     ]
-    .iter()
-    .cloned()
-    .collect();
+    .to_vec();
 
     let request = HttpClient::builder()
         .url(&url)
@@ -180,9 +176,10 @@ async fn test_connect_timeout() {
     let config = HttpConfig {
         connect_timeout: Some(3000), // 3000 millisecond.
         timeout: None,
+        ..Default::default()
     };
     let request = HttpClient::builder()
-        .url(&url)
+        .url(url)
         .method(method)
         .config(config)
         .build()
@@ -198,6 +195,7 @@ async fn test_timeout() {
     let config = HttpConfig {
         connect_timeout: None,
         timeout: Some(3000), // 3000 millisecond.
+        ..Default::default()
     };
     let request = HttpClient::builder()
         .url(&url)
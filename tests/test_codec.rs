@@ -0,0 +1,242 @@
+#[cfg(feature = "xml")]
+mod xml {
+    use feignhttp::{feign, post, Feign};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    pub struct User {
+        pub id: i32,
+        pub name: String,
+    }
+
+    #[post("http://localhost:1234/codec_xml", codec = "xml")]
+    pub async fn post_user(#[body] user: User) -> feignhttp::Result<User> {}
+
+    #[derive(Feign)]
+    pub struct XmlClient;
+
+    #[feign(url = "http://localhost:1234", codec = "xml")]
+    impl XmlClient {
+        #[post("/codec_xml_struct")]
+        pub async fn post_user(&self, #[body] user: User) -> feignhttp::Result<User> {}
+
+        #[post("/codec_xml_struct_text")]
+        pub async fn post_text(&self, #[body] text: &str) -> feignhttp::Result<String> {}
+    }
+}
+
+#[tokio::test]
+async fn test_xml() {
+    #[cfg(feature = "xml")]
+    {
+        use mockito::mock;
+        use xml::*;
+
+        let _mock = mock("POST", "/codec_xml")
+            .match_header("content-type", "application/xml")
+            .match_header("accept", "application/xml, text/xml")
+            .match_body("<User><id>1</id><name>jack</name></User>")
+            .with_header("content-type", "application/xml")
+            .with_body("<User><id>2</id><name>rose</name></User>")
+            .create();
+        let _mock_struct = mock("POST", "/codec_xml_struct")
+            .match_header("content-type", "application/xml")
+            .match_body("<User><id>3</id><name>tom</name></User>")
+            .with_header("content-type", "text/xml; charset=utf-8")
+            .with_body("<User><id>4</id><name>lucy</name></User>")
+            .create();
+        // Types implementing `IntoBody` and `FromResponse` are not encoded by the codec.
+        let _mock_text = mock("POST", "/codec_xml_struct_text")
+            .match_header("content-type", "text/plain")
+            .match_body("hello")
+            .with_body("world")
+            .create();
+
+        let user = post_user(User { id: 1, name: "jack".to_string() }).await.unwrap();
+        assert_eq!(user, User { id: 2, name: "rose".to_string() });
+
+        let user = XmlClient.post_user(User { id: 3, name: "tom".to_string() }).await.unwrap();
+        assert_eq!(user, User { id: 4, name: "lucy".to_string() });
+
+        assert_eq!(XmlClient.post_text("hello").await.unwrap(), "world");
+    }
+}
+
+#[tokio::test]
+async fn test_xml_decode_error() {
+    #[cfg(feature = "xml")]
+    {
+        use mockito::mock;
+        use xml::*;
+
+        let _mock = mock("POST", "/codec_xml")
+            .match_body("<User><id>5</id><name>jack</name></User>")
+            .with_header("content-type", "application/xml")
+            .with_body("<User><id>abc</id></User>")
+            .create();
+
+        let e = post_user(User { id: 5, name: "jack".to_string() }).await.unwrap_err();
+        assert!(e.is_decode_error());
+    }
+}
+
+#[cfg(feature = "json")]
+mod custom {
+    use feignhttp::codec::JsonCodec;
+    use feignhttp::{get, Decoder, Encoder};
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Serialize};
+
+    /// A vendor media type of JSON.
+    pub struct VendorCodec;
+
+    impl Encoder for VendorCodec {
+        fn content_type(&self) -> &'static str {
+            "application/vnd.test+json"
+        }
+
+        fn encode<T: Serialize + ?Sized>(&self, value: &T) -> feignhttp::Result<Vec<u8>> {
+            JsonCodec.encode(value)
+        }
+    }
+
+    impl Decoder for VendorCodec {
+        fn accept(&self) -> &'static str {
+            "application/vnd.test+json"
+        }
+
+        fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> feignhttp::Result<T> {
+            JsonCodec.decode(body)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    pub struct Item {
+        pub id: i32,
+    }
+
+    #[get("http://localhost:1234/codec_custom", codec = "VendorCodec")]
+    pub async fn get_item() -> feignhttp::Result<Item> {}
+}
+
+#[tokio::test]
+async fn test_custom_codec() {
+    #[cfg(feature = "json")]
+    {
+        use custom::*;
+        use mockito::mock;
+
+        let _mock = mock("GET", "/codec_custom")
+            .match_header("accept", "application/vnd.test+json")
+            .with_header("content-type", "application/vnd.test+json")
+            .with_body(r#"{"id":1}"#)
+            .create();
+
+        assert_eq!(get_item().await.unwrap(), Item { id: 1 });
+    }
+}
+
+#[cfg(all(feature = "msgpack", feature = "cbor"))]
+mod binary {
+    use feignhttp::{post, Cbor, MsgPack};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct User {
+        pub id: i32,
+        pub name: String,
+    }
+
+    #[post("http://localhost:1234/codec_msgpack", codec = "msgpack")]
+    pub async fn post_msgpack(#[body] user: User) -> feignhttp::Result<User> {}
+
+    #[post("http://localhost:1234/codec_cbor")]
+    pub async fn post_cbor(#[body(cbor)] user: User) -> feignhttp::Result<Cbor<User>> {}
+
+    #[post("http://localhost:1234/codec_msgpack_wrapper")]
+    pub async fn post_msgpack_wrapper(#[body] user: MsgPack<User>) -> feignhttp::Result<MsgPack<User>> {}
+}
+
+#[tokio::test]
+async fn test_msgpack_and_cbor() {
+    #[cfg(all(feature = "msgpack", feature = "cbor"))]
+    {
+        use binary::*;
+        use feignhttp::codec::{CborCodec, MsgPackCodec};
+        use feignhttp::{Cbor, Encoder, IntoBody, MsgPack};
+        use mockito::mock;
+
+        let user = User { id: 1, name: "jack".to_string() };
+
+        let _mock_msgpack = mock("POST", "/codec_msgpack")
+            .match_header("content-type", "application/msgpack")
+            .match_header("accept", "application/msgpack, application/x-msgpack, application/vnd.msgpack")
+            .with_header("content-type", "application/x-msgpack")
+            .with_body(MsgPackCodec.encode(&user).unwrap())
+            .create();
+        let _mock_cbor = mock("POST", "/codec_cbor")
+            .match_header("content-type", "application/cbor")
+            .match_header("accept", "application/cbor")
+            .with_header("content-type", "application/cbor")
+            .with_body(CborCodec.encode(&user).unwrap())
+            .create();
+        let _mock_wrapper = mock("POST", "/codec_msgpack_wrapper")
+            .match_header("content-type", "application/msgpack")
+            .with_body(MsgPack(&user).into_body().unwrap())
+            .create();
+
+        assert_eq!(post_msgpack(user.clone()).await.unwrap(), user);
+        assert_eq!(post_cbor(user.clone()).await.unwrap().into_inner(), user);
+        assert_eq!(*post_msgpack_wrapper(MsgPack(user.clone())).await.unwrap(), user);
+
+        // Structs are encoded as maps with field names.
+        let body = MsgPack(&user).into_body().unwrap();
+        assert_eq!(body[0], 0x82);
+        assert_eq!(Cbor(&user).into_body().unwrap(), CborCodec.encode(&user).unwrap());
+    }
+}
+
+#[cfg(feature = "protobuf")]
+mod protobuf {
+    use feignhttp::{post, Protobuf};
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Item {
+        #[prost(int32, tag = "1")]
+        pub id: i32,
+        #[prost(string, tag = "2")]
+        pub name: String,
+    }
+
+    #[post("http://localhost:1234/codec_protobuf")]
+    pub async fn post_item(#[body(protobuf)] item: Item) -> feignhttp::Result<Protobuf<Item>> {}
+}
+
+#[tokio::test]
+async fn test_protobuf() {
+    #[cfg(feature = "protobuf")]
+    {
+        use mockito::mock;
+        use prost::Message;
+        use protobuf::*;
+
+        let item = Item { id: 1, name: "jack".to_string() };
+
+        let _mock = mock("POST", "/codec_protobuf")
+            .match_header("content-type", "application/x-protobuf")
+            .match_header("accept", "application/x-protobuf, application/protobuf")
+            .with_header("content-type", "application/protobuf")
+            .with_body(item.encode_to_vec())
+            .create();
+        let _mock_invalid = mock("POST", "/codec_protobuf_invalid")
+            .with_header("content-type", "application/x-protobuf")
+            .with_body([0xff, 0xff])
+            .create();
+
+        assert_eq!(post_item(item.clone()).await.unwrap().into_inner(), item);
+
+        #[feignhttp::post("http://localhost:1234/codec_protobuf_invalid")]
+        async fn post_invalid(#[body(protobuf)] item: Item) -> feignhttp::Result<feignhttp::Protobuf<Item>> {}
+        assert!(post_invalid(item).await.unwrap_err().is_decode_error());
+    }
+}
@@ -0,0 +1,62 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use feignhttp::{get, post, HttpClient, HttpResponse};
+
+use mockito::{mock, server_address, Matcher};
+use std::io::Write;
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[tokio::test]
+async fn test_decompress_response() {
+    #[cfg(feature = "gzip")]
+    {
+        let _mock = mock("GET", "/gzip")
+            .match_header("accept-encoding", Matcher::Regex("gzip".into()))
+            .with_header("content-encoding", "gzip")
+            .with_body(gzip(b"Hello, i' m text"))
+            .create();
+
+        let url = format!("http://{}/gzip", server_address());
+        let request = HttpClient::builder()
+            .url(&url)
+            .method("GET")
+            .build()
+            .unwrap();
+        let text = request.send().await.unwrap().text().await.unwrap();
+
+        assert_eq!("Hello, i' m text", text);
+    }
+}
+
+#[post(url = "http://localhost:1234/post_gzip", compress = "gzip")]
+async fn post_gzip(#[body] text: String) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_compress_body() {
+    #[cfg(feature = "gzip")]
+    {
+        let body = gzip(b"I' m text");
+        let _mock = mock("POST", "/post_gzip")
+            .match_header("content-encoding", "gzip")
+            .match_header("content-type", "text/plain")
+            .match_body(String::from_utf8_lossy(&body).as_ref())
+            .create();
+
+        post_gzip("I' m text".to_string()).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_compress_not_enabled() {
+    #[cfg(not(feature = "gzip"))]
+    {
+        let err = post_gzip("I' m text".to_string()).await.unwrap_err();
+        assert!(err.is_config_error());
+    }
+}
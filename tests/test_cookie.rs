@@ -0,0 +1,138 @@
+use feignhttp::{feign, get, CookieJar, Feign, HttpClient};
+
+use mockito::{mock, server_address};
+
+#[test]
+fn test_jar_match() {
+    let jar = CookieJar::new();
+    jar.add_cookie_str("session=abc; Path=/api", "http://localhost/login");
+    jar.add_cookie_str("theme=dark; Domain=example.com", "http://www.example.com/");
+    jar.add_cookie_str("token=xyz; Secure", "http://localhost/");
+    jar.add_cookie_str("other=1; Domain=evil.com", "http://localhost/");
+
+    assert_eq!(jar.cookies("http://localhost/api/users"), Some("session=abc".to_string()));
+    assert_eq!(jar.cookies("http://localhost/apix"), None);
+    assert_eq!(jar.cookies("http://api.example.com/"), Some("theme=dark".to_string()));
+    assert_eq!(
+        jar.cookies("https://localhost/api"),
+        Some("session=abc; token=xyz".to_string())
+    );
+}
+
+#[test]
+fn test_jar_expires() {
+    let jar = CookieJar::new();
+    jar.add_cookie_str("session=abc", "http://localhost/");
+    jar.add_cookie_str("old=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT", "http://localhost/");
+    assert_eq!(jar.cookies("http://localhost/"), Some("session=abc".to_string()));
+
+    // Max-Age=0 removes the cookie.
+    jar.add_cookie_str("session=; Max-Age=0", "http://localhost/");
+    assert!(jar.is_empty());
+}
+
+#[test]
+fn test_jar_save_load() {
+    let jar = CookieJar::new();
+    jar.add_cookie_str("session=abc; HttpOnly", "http://localhost/api/login");
+    jar.add_cookie_str("theme=dark; Domain=example.com; Max-Age=3600", "http://example.com/");
+
+    let mut saved = Vec::new();
+    jar.save(&mut saved).unwrap();
+
+    let restored = CookieJar::load(saved.as_slice()).unwrap();
+    assert_eq!(restored.cookies("http://localhost/api/users"), Some("session=abc".to_string()));
+    assert_eq!(restored.cookies("http://www.example.com/"), Some("theme=dark".to_string()));
+}
+
+#[test]
+fn test_jar_load_error() {
+    assert!(CookieJar::load("localhost\tFALSE\t/".as_bytes()).is_err());
+}
+
+#[tokio::test]
+async fn test_client_cookie_jar() {
+    let _mock_login = mock("POST", "/client_login")
+        .with_header("set-cookie", "session=MTIzNDU2; Path=/")
+        .create();
+    let _mock_user = mock("GET", "/client_user")
+        .match_header("cookie", "session=MTIzNDU2; lang=en")
+        .create();
+
+    let jar = CookieJar::new();
+
+    let url = format!("http://{}/client_login", server_address());
+    let request = HttpClient::builder()
+        .url(&url)
+        .method("POST")
+        .cookie_jar(jar.clone())
+        .build()
+        .unwrap();
+    request.send().await.unwrap();
+
+    let url = format!("http://{}/client_user", server_address());
+    let request = HttpClient::builder()
+        .url(&url)
+        .method("GET")
+        .cookies(vec![("lang", "en".to_string())])
+        .cookie_jar(jar)
+        .build()
+        .unwrap();
+    request.send().await.unwrap();
+}
+
+#[get("http://localhost:1234/cookie")]
+async fn cookie(#[cookie] session: &str, #[cookie("lang")] language: String) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_cookie() {
+    let _mock = mock("GET", "/cookie")
+        .match_header("cookie", "session=MTIzNDU2; lang=en")
+        .create();
+
+    cookie("MTIzNDU2", "en".to_string()).await.unwrap();
+}
+
+#[derive(Feign)]
+struct Session {
+    #[cookie_jar]
+    jar: CookieJar,
+    #[cookie]
+    lang: &'static str,
+}
+
+#[feign(url = "http://localhost:1234/session")]
+impl Session {
+    #[post("/login")]
+    async fn login(&self, #[form] user: &str) -> feignhttp::Result<()> {}
+
+    #[get("/user")]
+    async fn user(&self) -> feignhttp::Result<String> {}
+}
+
+#[tokio::test]
+async fn test_feign_cookie_jar() {
+    let _mock_login = mock("POST", "/session/login")
+        .with_header("set-cookie", "session=MTIzNDU2; Path=/session; HttpOnly")
+        .create();
+    let _mock_user = mock("GET", "/session/user")
+        .match_header("cookie", "session=MTIzNDU2; lang=en")
+        .with_body("jack")
+        .create();
+
+    let session = Session {
+        jar: CookieJar::new(),
+        lang: "en",
+    };
+    session.login("jack").await.unwrap();
+    assert_eq!(session.user().await.unwrap(), "jack");
+
+    // The jar can be saved and restored for a new session.
+    let mut saved = Vec::new();
+    session.jar.save(&mut saved).unwrap();
+    let session = Session {
+        jar: CookieJar::load(saved.as_slice()).unwrap(),
+        lang: "en",
+    };
+    assert_eq!(session.user().await.unwrap(), "jack");
+}
@@ -0,0 +1,143 @@
+#[cfg(feature = "csv")]
+mod csv {
+    use feignhttp::{feign, get, Csv, CsvStream, Feign};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    pub struct Row {
+        pub id: i32,
+        pub name: String,
+    }
+
+    #[get("http://localhost:1234/csv")]
+    pub async fn rows() -> feignhttp::Result<Csv<Row>> {}
+
+    #[get("http://localhost:1234/csv_stream")]
+    pub async fn row_stream() -> feignhttp::Result<CsvStream<Row>> {}
+
+    #[get("http://localhost:1234/csv_semicolon", csv_delimiter = ";", csv_quote = "'")]
+    pub async fn rows_semicolon() -> feignhttp::Result<Csv<Row>> {}
+
+    #[get("http://localhost:1234/csv_invalid")]
+    pub async fn rows_invalid() -> feignhttp::Result<Csv<Row>> {}
+
+    #[get("http://localhost:1234/csv_stream_{name}")]
+    pub async fn row_stream_invalid(#[path] name: &str) -> feignhttp::Result<CsvStream<Row>> {}
+
+    #[derive(Feign)]
+    pub struct CsvClient;
+
+    #[feign(url = "http://localhost:1234", csv_delimiter = "\t", csv_headers = "false")]
+    impl CsvClient {
+        #[get("/csv_tsv")]
+        pub async fn rows(&self) -> feignhttp::Result<Csv<(i32, String)>> {}
+
+        #[get("/csv_tsv_stream")]
+        pub async fn row_stream(&self) -> feignhttp::Result<CsvStream<(i32, String)>> {}
+    }
+}
+
+#[tokio::test]
+async fn test_csv() {
+    #[cfg(feature = "csv")]
+    {
+        use self::csv::*;
+        use futures_util::StreamExt;
+        use mockito::mock;
+
+        let _mock = mock("GET", "/csv")
+            .match_header("accept", "text/csv")
+            .with_header("content-type", "text/csv; charset=utf-8")
+            .with_body("id,name\n1,jack\n2,\"rose, \"\"jr\"\"\"\n")
+            .create();
+        let _mock_stream = mock("GET", "/csv_stream")
+            .with_header("content-type", "text/csv")
+            .with_body_from_fn(|w| {
+                w.write_all(b"name,id\r\njack,")?;
+                w.write_all(b"1\r\n\"multi\nline\",2\r\n")?;
+                w.write_all(b"\r\nrose,3")
+            })
+            .create();
+
+        let rows = rows().await.unwrap();
+        assert_eq!(
+            rows.into_inner(),
+            vec![
+                Row { id: 1, name: "jack".to_string() },
+                Row { id: 2, name: "rose, \"jr\"".to_string() },
+            ]
+        );
+
+        // Fields are matched by the header row, and blank lines are skipped.
+        let rows: Vec<Row> = row_stream().await.unwrap().map(|row| row.unwrap()).collect().await;
+        assert_eq!(
+            rows,
+            vec![
+                Row { id: 1, name: "jack".to_string() },
+                Row { id: 2, name: "multi\nline".to_string() },
+                Row { id: 3, name: "rose".to_string() },
+            ]
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_csv_config() {
+    #[cfg(feature = "csv")]
+    {
+        use self::csv::*;
+        use futures_util::StreamExt;
+        use mockito::mock;
+
+        let _mock = mock("GET", "/csv_semicolon")
+            .with_header("content-type", "text/csv")
+            .with_body("id;name\n1;'jack;rose'\n")
+            .create();
+        let _mock_tsv = mock("GET", "/csv_tsv")
+            .with_header("content-type", "text/csv")
+            .with_body("1\tjack\n2\trose\n")
+            .create();
+        let _mock_tsv_stream = mock("GET", "/csv_tsv_stream")
+            .with_header("content-type", "text/csv")
+            .with_body("1\tjack\n2\trose\n")
+            .create();
+
+        let rows = rows_semicolon().await.unwrap();
+        assert_eq!(*rows, vec![Row { id: 1, name: "jack;rose".to_string() }]);
+
+        let expected = vec![(1, "jack".to_string()), (2, "rose".to_string())];
+        assert_eq!(CsvClient.rows().await.unwrap().into_inner(), expected);
+        let rows: Vec<_> = CsvClient.row_stream().await.unwrap().map(|row| row.unwrap()).collect().await;
+        assert_eq!(rows, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_csv_error() {
+    #[cfg(feature = "csv")]
+    {
+        use self::csv::*;
+        use futures_util::StreamExt;
+        use mockito::mock;
+
+        let _mock = mock("GET", "/csv_stream_row")
+            .with_header("content-type", "text/csv")
+            .with_body("id,name\n1,jack\nx,rose\n3,tom\n")
+            .create();
+        let _mock_buffered = mock("GET", "/csv_invalid")
+            .with_header("content-type", "text/csv")
+            .with_body("id,name\n1,jack\nx,rose\n")
+            .create();
+
+        let rows: Vec<_> = row_stream_invalid("row").await.unwrap().collect().await;
+        assert_eq!(rows.len(), 3);
+        let e = rows[1].as_ref().unwrap_err();
+        assert!(e.is_decode_error());
+        assert!(e.to_string().contains("invalid csv at row 2"));
+        assert_eq!(rows[2].as_ref().unwrap(), &Row { id: 3, name: "tom".to_string() });
+
+        let e = rows_invalid().await.unwrap_err();
+        assert!(e.is_decode_error());
+        assert!(e.to_string().contains("invalid csv at row 2"));
+    }
+}
@@ -0,0 +1,149 @@
+use feignhttp::{get, Download};
+use mockito::mock;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[get("http://localhost:1234/download/{name}")]
+async fn download_file(#[path] name: &str, #[download] dest: &Path) -> feignhttp::Result<Download> {}
+
+#[get("http://localhost:1234/download/{name}")]
+async fn download_to(#[path] name: &str, #[download] dest: PathBuf) -> feignhttp::Result<Download> {}
+
+/// An empty directory in the temporary directory.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("feignhttp_download_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_download() {
+    let _mock = mock("GET", "/download/file.txt")
+        .with_header("etag", "\"v1\"")
+        .with_body("hello world")
+        .create();
+
+    let dir = temp_dir("file");
+    let path = dir.join("file.txt");
+    let download = download_file("file.txt", &path).await.unwrap();
+    assert_eq!(download, Download { path: path.clone(), len: 11, resumed: false });
+    assert_eq!(fs::read_to_string(&path).unwrap(), "hello world");
+    // The partial file and the etag are removed.
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+}
+
+#[tokio::test]
+async fn test_download_resume() {
+    let _mock = mock("GET", "/download/resume.txt")
+        .match_header("range", "bytes=6-")
+        .match_header("if-range", "\"v1\"")
+        .with_status(206)
+        .with_header("etag", "\"v1\"")
+        .with_header("content-range", "bytes 6-10/11")
+        .with_body("world")
+        .create();
+
+    let dir = temp_dir("resume");
+    let path = dir.join("resume.txt");
+    fs::write(dir.join("resume.txt.part"), "hello ").unwrap();
+    fs::write(dir.join("resume.txt.part.etag"), "\"v1\"").unwrap();
+
+    let download = download_to("resume.txt", path.clone()).await.unwrap();
+    assert_eq!(download, Download { path: path.clone(), len: 11, resumed: true });
+    assert_eq!(fs::read_to_string(&path).unwrap(), "hello world");
+    assert!(!dir.join("resume.txt.part.etag").exists());
+}
+
+#[tokio::test]
+async fn test_download_changed() {
+    // The file is changed, so the server ignores the range.
+    let _mock = mock("GET", "/download/changed.txt")
+        .match_header("if-range", "\"v1\"")
+        .with_header("etag", "\"v2\"")
+        .with_body("hello rust")
+        .create();
+    let _mock_unsatisfiable = mock("GET", "/download/unsatisfiable.txt")
+        .match_header("range", "bytes=20-")
+        .with_status(416)
+        .create();
+    let _mock_full = mock("GET", "/download/unsatisfiable.txt")
+        .with_body("hello world")
+        .create();
+
+    let dir = temp_dir("changed");
+    let path = dir.join("changed.txt");
+    fs::write(dir.join("changed.txt.part"), "hello ").unwrap();
+    fs::write(dir.join("changed.txt.part.etag"), "\"v1\"").unwrap();
+    let download = download_file("changed.txt", &path).await.unwrap();
+    assert_eq!(download, Download { path: path.clone(), len: 10, resumed: false });
+    assert_eq!(fs::read_to_string(&path).unwrap(), "hello rust");
+
+    // The partial file is longer than the file.
+    let path = dir.join("unsatisfiable.txt");
+    fs::write(dir.join("unsatisfiable.txt.part"), "hello world, hello world").unwrap();
+    fs::write(dir.join("unsatisfiable.txt.part.etag"), "\"v1\"").unwrap();
+    let download = download_file("unsatisfiable.txt", &path).await.unwrap();
+    assert_eq!(download.len, 11);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "hello world");
+}
+
+#[tokio::test]
+async fn test_download_dir() {
+    let _mock = mock("GET", "/download/report")
+        .with_header("content-disposition", "attachment; filename=\"report.csv\"")
+        .with_body("a,b")
+        .create();
+    let _mock_utf8 = mock("GET", "/download/resume")
+        .with_header(
+            "content-disposition",
+            "attachment; filename=\"resume.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf",
+        )
+        .with_body("pdf")
+        .create();
+    let _mock_evil = mock("GET", "/download/evil")
+        .with_header("content-disposition", "attachment; filename=\"../evil.txt\"")
+        .with_body("evil")
+        .create();
+    let _mock_url = mock("GET", "/download/data.json").with_body("{}").create();
+
+    let dir = temp_dir("dir");
+    let download = download_file("report", &dir).await.unwrap();
+    assert_eq!(download.path, dir.join("report.csv"));
+    assert_eq!(fs::read_to_string(dir.join("report.csv")).unwrap(), "a,b");
+
+    let download = download_to("resume", dir.clone()).await.unwrap();
+    assert_eq!(download.path, dir.join("résumé.pdf"));
+
+    // A file name can't leave the directory.
+    let download = download_to("evil", dir.clone()).await.unwrap();
+    assert_eq!(download.path, dir.join("evil.txt"));
+
+    let download = download_to("data.json", dir.clone()).await.unwrap();
+    assert_eq!(download.path, dir.join("data.json"));
+}
+
+#[tokio::test]
+async fn test_download_error() {
+    let _mock = mock("GET", "/download/range.txt")
+        .with_status(206)
+        .with_header("content-range", "bytes 0-10/11")
+        .with_body("hello world")
+        .create();
+    let _mock_status = mock("GET", "/download/missing.txt").with_status(404).create();
+
+    let dir = temp_dir("error");
+    let path = dir.join("range.txt");
+    fs::write(dir.join("range.txt.part"), "hello ").unwrap();
+    fs::write(dir.join("range.txt.part.etag"), "\"v1\"").unwrap();
+    let e = download_file("range.txt", &path).await.unwrap_err();
+    assert!(e.is_decode_error());
+    assert!(e.to_string().contains("unexpected content range"));
+    // The partial file is kept for the next attempt.
+    assert_eq!(fs::read_to_string(dir.join("range.txt.part")).unwrap(), "hello ");
+    assert!(!path.exists());
+
+    let e = download_to("missing.txt", dir.join("missing.txt")).await.unwrap_err();
+    assert!(e.is_status_error());
+    assert!(!dir.join("missing.txt").exists());
+}
@@ -47,6 +47,23 @@ async fn test_header() {
         .unwrap();
 }
 
+#[get(
+    "http://localhost:1234",
+    path = concat!("/get_", "comma"),
+    headers = "accept: text/html, application/json",
+    timeout = 3000,
+)]
+async fn get_comma() -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_comma_metadata() {
+    let _mock = mock("GET", "/get_comma")
+        .match_header("accept", "text/html, application/json")
+        .create();
+
+    get_comma().await.unwrap();
+}
+
 #[post(url = "http://localhost:1234/post_query")]
 async fn post_query(#[query] id: u32, #[query("name")] name: String) -> feignhttp::Result<String> {}
 
@@ -86,6 +103,7 @@ async fn test_send_text() {
     post_text("I' m text".to_string()).await.unwrap();
 }
 
+#[allow(dead_code)]
 #[derive(Serialize)]
 struct User {
     id: i32,
@@ -125,3 +143,71 @@ async fn test_send_vec() {
 
     post_data(vec![97, 97, 97]).await.unwrap();
 }
+
+#[get("http://localhost:1234/repos/{owner}/{repo}/issues{?page,per_page}")]
+async fn issues(
+    #[path] owner: &str,
+    #[param] repo: &str,
+    #[param] page: u32,
+    #[param] per_page: u32,
+) -> feignhttp::Result<String> {
+}
+
+#[tokio::test]
+async fn test_template_query() {
+    let _mock = mock("GET", "/repos/dxx/feignhttp/issues?page=2&per_page=10").create();
+
+    issues("dxx", "feignhttp", 2, 10).await.unwrap();
+}
+
+#[get("http://localhost:1234", path = "/files{/ids*}{+rest}")]
+async fn files(#[path] ids: Vec<u32>, #[param] rest: &str) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_template_list() {
+    let _mock = mock("GET", "/files/1/2/3/a/b.txt").create();
+
+    files(vec![1, 2, 3], "/a/b.txt").await.unwrap();
+}
+
+#[get(
+    "http://localhost:1234/query_meta",
+    query = "api-version=2023-01-01&format={fmt}&pretty"
+)]
+async fn query_meta(#[param] fmt: &str, #[query] id: u32) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_query_metadata() {
+    let _mock = mock("GET", "/query_meta?api-version=2023-01-01&format=json&pretty=&id=1").create();
+
+    query_meta("json", 1).await.unwrap();
+}
+
+#[post(
+    url = "http://localhost:1234/post_body_json",
+    body = r#"{"user":"{name}","id":{id}}"#,
+    content_type = "application/json"
+)]
+async fn post_body_json(#[param] name: &str, #[param] id: u32) -> feignhttp::Result<String> {}
+
+#[post(
+    url = "http://localhost:1234/post_body_form",
+    body = "user={name}&id={id}",
+    content_type = "application/x-www-form-urlencoded"
+)]
+async fn post_body_form(#[param] name: &str, #[param] id: u32) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_body_template() {
+    let _mock_json = mock("POST", "/post_body_json")
+        .match_header("content-type", "application/json")
+        .match_body(r#"{"user":"ja\"ck","id":1}"#)
+        .create();
+    let _mock_form = mock("POST", "/post_body_form")
+        .match_header("content-type", "application/x-www-form-urlencoded")
+        .match_body("user=ja%26ck&id=1")
+        .create();
+
+    post_body_json("ja\"ck", 1).await.unwrap();
+    post_body_form("ja&ck", 1).await.unwrap();
+}
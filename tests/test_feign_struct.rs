@@ -60,3 +60,73 @@ async fn test_feign_client() {
     client.home().await.unwrap();
     client.repository().await.unwrap();
 }
+
+#[derive(Feign)]
+struct Tenant {
+    #[url_path]
+    tenant: &'static str,
+}
+
+#[feign(url = "http://localhost:1234/{tenant}")]
+impl Tenant {
+    #[get("/users/{id}")]
+    async fn user(&self, #[path] id: u32) -> feignhttp::Result<String> {}
+
+    #[get("/groups/{group}")]
+    async fn group(&self) -> feignhttp::Result<String> {}
+}
+
+#[tokio::test]
+async fn test_url_path() {
+    let _mock = mock("GET", "/acme/users/1").create();
+
+    let tenant = Tenant { tenant: "acme" };
+    tenant.user(1).await.unwrap();
+
+    // `{group}` is neither a `#[path]` argument nor a `#[url_path]` field.
+    assert!(tenant.group().await.unwrap_err().is_build_error());
+}
+
+#[derive(Feign)]
+struct Search {
+    #[url_path]
+    scopes: Vec<&'static str>,
+    #[param]
+    per_page: u32,
+}
+
+#[feign(url = "http://localhost:1234/search{/scopes*}")]
+impl Search {
+    #[get("{?q,per_page}")]
+    async fn search(&self, #[param] q: &str) -> feignhttp::Result<String> {}
+}
+
+#[tokio::test]
+async fn test_url_template() {
+    let _mock = mock("GET", "/search/code/rust?q=feign%20http&per_page=5").create();
+
+    let search = Search {
+        scopes: vec!["code", "rust"],
+        per_page: 5,
+    };
+    search.search("feign http").await.unwrap();
+}
+
+#[derive(Feign)]
+struct Versioned {
+    #[query]
+    tenant: &'static str,
+}
+
+#[feign(url = "http://localhost:1234", query = "api-version=2023-01-01")]
+impl Versioned {
+    #[get("/items", query = "format=json")]
+    async fn items(&self, #[query] page: u32) -> feignhttp::Result<String> {}
+}
+
+#[tokio::test]
+async fn test_query_metadata() {
+    let _mock = mock("GET", "/items?api-version=2023-01-01&format=json&tenant=a&page=2").create();
+
+    Versioned { tenant: "a" }.items(2).await.unwrap();
+}
@@ -0,0 +1,72 @@
+use feignhttp::{feign, get, Feign};
+
+use mockito::mock;
+
+#[get("http://localhost:1234/files/{name}")]
+async fn file(#[path] name: &str) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_encode_path() {
+    let _mock_slash = mock("GET", "/files/a%2Fb").with_body("slash").create();
+    let _mock_dot = mock("GET", "/files/..%2Fadmin").with_body("dot").create();
+    let _mock_query = mock("GET", "/files/%3Fx%3D1").with_body("query").create();
+    let _mock_space = mock("GET", "/files/a%20b%23c").with_body("space").create();
+
+    assert_eq!(file("a/b").await.unwrap(), "slash");
+    assert_eq!(file("../admin").await.unwrap(), "dot");
+    assert_eq!(file("?x=1").await.unwrap(), "query");
+    assert_eq!(file("a b#c").await.unwrap(), "space");
+}
+
+#[tokio::test]
+async fn test_dot_segment() {
+    assert!(file("..").await.unwrap_err().is_build_error());
+    assert!(file(".").await.unwrap_err().is_build_error());
+}
+
+#[get("http://localhost:1234/raw/{path}")]
+async fn raw(#[path(raw)] path: &str) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_raw_path() {
+    let _mock = mock("GET", "/raw/a/b/c.txt").create();
+
+    raw("a/b/c.txt").await.unwrap();
+}
+
+#[get("http://localhost:1234/header", headers = "x-name: {name}")]
+async fn header(#[param] name: &str, #[header] token: &str) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_header_crlf() {
+    let err = header("jack\r\nx-admin: true", "MTIzNDU2").await.unwrap_err();
+    assert!(err.is_build_error());
+
+    let err = header("jack", "MTIzNDU2\nx-admin: true").await.unwrap_err();
+    assert!(err.is_build_error());
+}
+
+#[derive(Feign)]
+struct Repository {
+    #[url_path]
+    owner: String,
+    #[url_path(raw)]
+    prefix: &'static str,
+}
+
+#[feign(url = "http://localhost:1234/{prefix}/repos/{owner}")]
+impl Repository {
+    #[get("/{repo}")]
+    async fn get(&self, #[path] repo: &str) -> feignhttp::Result<String> {}
+}
+
+#[tokio::test]
+async fn test_struct_encode_path() {
+    let _mock = mock("GET", "/api/v3/repos/a%2Fb/c%3Fd").create();
+
+    let repository = Repository {
+        owner: "a/b".to_string(),
+        prefix: "api/v3",
+    };
+    repository.get("c?d").await.unwrap();
+}
@@ -0,0 +1,71 @@
+use feignhttp::get;
+use feignhttp::util::join_url;
+
+use mockito::mock;
+
+#[test]
+fn test_join_slash() {
+    assert_eq!(join_url("http://xxx", "/users"), "http://xxx/users");
+    assert_eq!(join_url("http://xxx/", "/users"), "http://xxx/users");
+    assert_eq!(join_url("http://xxx/", "users"), "http://xxx/users");
+    assert_eq!(join_url("http://xxx", "users"), "http://xxx/users");
+    assert_eq!(join_url("http://xxx/users", "{id}"), "http://xxx/users/{id}");
+    assert_eq!(join_url("http://xxx/users", "{/id}"), "http://xxx/users{/id}");
+    assert_eq!(join_url("http://xxx/users/", ""), "http://xxx/users/");
+}
+
+#[test]
+fn test_join_base_path() {
+    assert_eq!(join_url("http://xxx/api/v2", "/users"), "http://xxx/api/v2/users");
+    assert_eq!(join_url("http://xxx/api/v2/", "/users/"), "http://xxx/api/v2/users/");
+}
+
+#[test]
+fn test_join_query() {
+    assert_eq!(
+        join_url("http://xxx/api?key=1", "/users"),
+        "http://xxx/api/users?key=1"
+    );
+    assert_eq!(
+        join_url("http://xxx/api?key=1", "/users?page=2"),
+        "http://xxx/api/users?key=1&page=2"
+    );
+    assert_eq!(join_url("http://xxx/api", "?page=2"), "http://xxx/api?page=2");
+    assert_eq!(
+        join_url("http://xxx/api?key=1", "/search{?q,page}"),
+        "http://xxx/api/search?key=1{&q,page}"
+    );
+    assert_eq!(join_url("http://xxx/api", "/search{?q}"), "http://xxx/api/search{?q}");
+}
+
+#[test]
+fn test_join_fragment() {
+    assert_eq!(join_url("http://xxx/api#top", "/users"), "http://xxx/api/users#top");
+    assert_eq!(
+        join_url("http://xxx/api?key=1#top", "/users#list"),
+        "http://xxx/api/users?key=1#list"
+    );
+}
+
+#[test]
+fn test_join_env_placeholder() {
+    assert_eq!(
+        join_url("${HOST:http://xxx?a=1}", "/users"),
+        "${HOST:http://xxx?a=1}/users"
+    );
+}
+
+#[get("http://localhost:1234/api/v2/", path = "/users")]
+async fn users() -> feignhttp::Result<String> {}
+
+#[get("http://localhost:1234/api?key=1", path = "/search{?q}")]
+async fn search(#[param] q: &str) -> feignhttp::Result<String> {}
+
+#[tokio::test]
+async fn test_join_request() {
+    let _mock_users = mock("GET", "/api/v2/users").create();
+    let _mock_search = mock("GET", "/api/search?key=1&q=rust").create();
+
+    users().await.unwrap();
+    search("rust").await.unwrap();
+}
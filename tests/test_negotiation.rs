@@ -0,0 +1,120 @@
+use feignhttp::{feign, get, post, Feign, Text};
+
+use mockito::{mock, Matcher};
+
+#[post(
+    "http://localhost:1234/negotiate",
+    consumes = "application/xml",
+    produces = "text/plain"
+)]
+async fn post_xml(#[body] xml: &str) -> feignhttp::Result<String> {}
+
+#[get("http://localhost:1234/negotiate_text", produces = "text/plain")]
+async fn get_text() -> feignhttp::Result<Text> {}
+
+#[tokio::test]
+async fn test_consumes_produces() {
+    let _mock = mock("POST", "/negotiate")
+        .match_header("content-type", "application/xml")
+        .match_header("accept", "text/plain")
+        .match_body("<id>1</id>")
+        .with_header("content-type", "text/plain; charset=utf-8")
+        .with_body("ok")
+        .create();
+
+    assert_eq!(post_xml("<id>1</id>").await.unwrap(), "ok");
+}
+
+#[tokio::test]
+async fn test_unexpected_content_type() {
+    let _mock = mock("GET", "/negotiate_text")
+        .match_header("accept", "text/plain")
+        .with_header("content-type", "text/html")
+        .with_body("<p>ok</p>")
+        .create();
+
+    let e = get_text().await.unwrap_err();
+    assert!(e.is_decode_error());
+    assert!(e.to_string().contains("unexpected content type text/html, expected text/plain"));
+}
+
+#[derive(Feign)]
+struct Negotiation;
+
+#[feign(url = "http://localhost:1234", consumes = "text/csv", produces = "text/*")]
+impl Negotiation {
+    #[post("/negotiate_struct")]
+    async fn post_csv(&self, #[body] csv: &str) -> feignhttp::Result<String> {}
+
+    #[get("/negotiate_struct", produces = "text/plain")]
+    async fn get_plain(&self) -> feignhttp::Result<String> {}
+}
+
+#[tokio::test]
+async fn test_struct_consumes_produces() {
+    let _mock_post = mock("POST", "/negotiate_struct")
+        .match_header("content-type", "text/csv")
+        .match_header("accept", "text/*")
+        .with_header("content-type", "text/csv")
+        .with_body("a,b")
+        .create();
+    let _mock_get = mock("GET", "/negotiate_struct")
+        .match_header("accept", "text/plain")
+        .with_header("content-type", "text/plain")
+        .with_body("c")
+        .create();
+
+    assert_eq!(Negotiation.post_csv("a,b").await.unwrap(), "a,b");
+    assert_eq!(Negotiation.get_plain().await.unwrap(), "c");
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use feignhttp::{get, Json};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    pub struct User {
+        pub id: i32,
+    }
+
+    #[get("http://localhost:1234/negotiate_json")]
+    pub async fn get_user() -> feignhttp::Result<User> {}
+
+    #[get("http://localhost:1234/negotiate_json_wrapper", headers = "accept: application/vnd.api+json")]
+    pub async fn get_user_json() -> feignhttp::Result<Json<User>> {}
+}
+
+#[tokio::test]
+async fn test_default_accept() {
+    #[cfg(feature = "json")]
+    {
+        use json::*;
+
+        let _mock = mock("GET", "/negotiate_json")
+            .match_header("accept", "application/json")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id":1}"#)
+            .create();
+        let _mock_wrapper = mock("GET", "/negotiate_json_wrapper")
+            .match_header("accept", "application/vnd.api+json")
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(r#"{"id":2}"#)
+            .create();
+
+        assert_eq!(get_user().await.unwrap(), User { id: 1 });
+        assert_eq!(get_user_json().await.unwrap().id, 2);
+    }
+
+    // Only the default `accept` header of the client is sent for a return type without a media type.
+    let _mock = mock("GET", "/negotiate_none")
+        .match_header(
+            "accept",
+            Matcher::AnyOf(vec![Matcher::Missing, Matcher::Exact("*/*".to_string())]),
+        )
+        .with_body("ok")
+        .create();
+    #[get("http://localhost:1234/negotiate_none")]
+    async fn get_none() -> feignhttp::Result<String> {}
+    assert_eq!(get_none().await.unwrap(), "ok");
+}
@@ -0,0 +1,184 @@
+#[cfg(feature = "json")]
+mod paginate {
+    use feignhttp::{feign, get, paginate, Feign};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    pub struct Issue {
+        pub id: i32,
+    }
+
+    #[get("http://localhost:1234/pages_link")]
+    #[paginate(link_header)]
+    pub async fn issues() -> feignhttp::Result<Vec<Issue>> {}
+
+    #[get("http://localhost:1234/pages_cursor")]
+    #[paginate(cursor = "next_cursor", param = "after", items = "data")]
+    pub async fn issues_by_cursor(#[query] state: &str) -> feignhttp::Result<Vec<Issue>> {}
+
+    #[paginate(page = "page", max_pages = 5)]
+    #[get("http://localhost:1234/pages_number")]
+    pub async fn issues_by_page(#[query] per_page: u32) -> feignhttp::Result<Vec<Issue>> {}
+
+    #[get("http://localhost:1234/pages_endless")]
+    #[paginate(link_header, max_pages = 2)]
+    pub async fn endless() -> feignhttp::Result<Vec<Issue>> {}
+
+    #[get("http://localhost:1234/pages_{name}")]
+    #[paginate(link_header)]
+    pub async fn issues_invalid(#[path] name: &str) -> feignhttp::Result<Vec<Issue>> {}
+
+    #[derive(Feign)]
+    pub struct Repository {
+        #[url_path]
+        pub name: String,
+    }
+
+    #[feign(url = "http://localhost:1234/pages_repos/{name}")]
+    impl Repository {
+        #[paginate(page = "page")]
+        #[get("/issues")]
+        pub async fn issues(&self, #[query] page: u32) -> feignhttp::Result<Vec<Issue>> {}
+    }
+}
+
+#[tokio::test]
+async fn test_paginate_link_header() {
+    #[cfg(feature = "json")]
+    {
+        use futures_util::StreamExt;
+        use mockito::mock;
+        use paginate::*;
+
+        let _mock = mock("GET", "/pages_link")
+            .match_header("accept", "application/json")
+            .with_header("content-type", "application/json")
+            .with_header(
+                "link",
+                r#"<http://localhost:1234/pages_link?page=2>; rel="next", <http://localhost:1234/pages_link?page=3>; rel="last""#,
+            )
+            .with_body(r#"[{"id":1},{"id":2}]"#)
+            .create();
+        // A relative link is resolved against the request url.
+        let _mock_page2 = mock("GET", "/pages_link?page=2")
+            .with_header("content-type", "application/json")
+            .with_header("link", r#"</pages_link?page=1>; rel="prev first", </pages_link?page=3>; rel="next""#)
+            .with_body(r#"[{"id":3}]"#)
+            .create();
+        let _mock_page3 = mock("GET", "/pages_link?page=3")
+            .with_header("content-type", "application/json")
+            .with_body("[]")
+            .create();
+
+        let issues: Vec<Issue> = issues().map(|issue| issue.unwrap()).collect().await;
+        assert_eq!(issues, vec![Issue { id: 1 }, Issue { id: 2 }, Issue { id: 3 }]);
+    }
+}
+
+#[tokio::test]
+async fn test_paginate_cursor() {
+    #[cfg(feature = "json")]
+    {
+        use futures_util::StreamExt;
+        use mockito::mock;
+        use paginate::*;
+
+        let _mock = mock("GET", "/pages_cursor?state=open")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":[{"id":1}],"next_cursor":"abc"}"#)
+            .create();
+        let _mock_page2 = mock("GET", "/pages_cursor?state=open&after=abc")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":[{"id":2}],"next_cursor":7}"#)
+            .create();
+        let _mock_page3 = mock("GET", "/pages_cursor?state=open&after=7")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":[{"id":3}],"next_cursor":null}"#)
+            .create();
+
+        let issues: Vec<Issue> = issues_by_cursor("open").map(|issue| issue.unwrap()).collect().await;
+        assert_eq!(issues, vec![Issue { id: 1 }, Issue { id: 2 }, Issue { id: 3 }]);
+    }
+}
+
+#[tokio::test]
+async fn test_paginate_page() {
+    #[cfg(feature = "json")]
+    {
+        use futures_util::StreamExt;
+        use mockito::mock;
+        use paginate::*;
+
+        let _mock = mock("GET", "/pages_number?per_page=2")
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"id":1},{"id":2}]"#)
+            .create();
+        let _mock_page2 = mock("GET", "/pages_number?per_page=2&page=2")
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"id":3}]"#)
+            .create();
+        let _mock_page3 = mock("GET", "/pages_number?per_page=2&page=3")
+            .with_header("content-type", "application/json")
+            .with_body("[]")
+            .create();
+        // The first page is the page of the query.
+        let _mock_repo = mock("GET", "/pages_repos/feign/issues?page=5")
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"id":5}]"#)
+            .create();
+        let _mock_repo_page6 = mock("GET", "/pages_repos/feign/issues?page=6")
+            .with_header("content-type", "application/json")
+            .with_body("[]")
+            .create();
+
+        let issues: Vec<Issue> = issues_by_page(2).map(|issue| issue.unwrap()).collect().await;
+        assert_eq!(issues, vec![Issue { id: 1 }, Issue { id: 2 }, Issue { id: 3 }]);
+
+        let repository = Repository { name: "feign".to_string() };
+        let issues: Vec<Issue> = repository.issues(5).map(|issue| issue.unwrap()).collect().await;
+        assert_eq!(issues, vec![Issue { id: 5 }]);
+    }
+}
+
+#[tokio::test]
+async fn test_paginate_error() {
+    #[cfg(feature = "json")]
+    {
+        use futures_util::StreamExt;
+        use mockito::mock;
+        use paginate::*;
+
+        let _mock = mock("GET", "/pages_endless")
+            .with_header("content-type", "application/json")
+            .with_header("link", r#"<http://localhost:1234/pages_endless>; rel="next""#)
+            .with_body(r#"[{"id":1}]"#)
+            .create();
+        let _mock_status = mock("GET", "/pages_status").with_status(500).create();
+        let _mock_page2 = mock("GET", "/pages_status?page=2").with_status(500).create();
+        let _mock_decode = mock("GET", "/pages_decode")
+            .with_header("content-type", "application/json")
+            .with_header("link", r#"</pages_status?page=2>; rel="next""#)
+            .with_body(r#"[{"id":1}]"#)
+            .create();
+
+        // The maximum number of pages is reached while there are more pages.
+        let items: Vec<_> = endless().collect().await;
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[1].as_ref().unwrap(), &Issue { id: 1 });
+        let e = items[2].as_ref().unwrap_err();
+        assert!(e.is_request_error());
+        assert!(e.to_string().contains("pagination exceeds the maximum of 2 pages"));
+
+        let items: Vec<_> = endless().max_pages(1).collect().await;
+        assert_eq!(items.len(), 2);
+
+        let items: Vec<_> = issues_invalid("status").collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].as_ref().unwrap_err().is_status_error());
+
+        // An error of a later page ends the stream.
+        let items: Vec<_> = issues_invalid("decode").collect().await;
+        assert_eq!(items.len(), 2);
+        assert!(items[1].as_ref().unwrap_err().is_status_error());
+    }
+}
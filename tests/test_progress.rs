@@ -0,0 +1,80 @@
+use feignhttp::{get, post, Download, Progress};
+use mockito::mock;
+use std::sync::{Arc, Mutex};
+
+#[post("http://localhost:1234/progress/upload")]
+async fn upload(#[body] data: Vec<u8>, #[progress] progress: impl Fn(Progress)) -> feignhttp::Result<String> {}
+
+#[get("http://localhost:1234/progress/{name}")]
+async fn fetch(#[path] name: &str, #[progress] progress: impl Fn(Progress)) -> feignhttp::Result<Vec<u8>> {}
+
+#[get("http://localhost:1234/progress/{name}")]
+async fn download(
+    #[path] name: &str,
+    #[download] dest: &std::path::Path,
+    #[progress] progress: impl Fn(Progress) + Send,
+) -> feignhttp::Result<Download> {}
+
+/// A callback which keeps the reported progress.
+fn recorder() -> (Arc<Mutex<Vec<Progress>>>, impl Fn(Progress)) {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let recorded = reports.clone();
+    (reports, move |progress| recorded.lock().unwrap().push(progress))
+}
+
+#[tokio::test]
+async fn test_progress_upload() {
+    let data = vec![b'a'; 200 * 1024];
+    let _mock = mock("POST", "/progress/upload")
+        .match_header("content-length", "204800")
+        .match_body(mockito::Matcher::Exact(String::from_utf8(data.clone()).unwrap()))
+        .with_body("ok")
+        .create();
+
+    let (reports, callback) = recorder();
+    let text = upload(data, callback).await.unwrap();
+    assert_eq!(text, "ok");
+
+    let reports = reports.lock().unwrap();
+    // The body is reported as it is sent, not at once.
+    let mut sent: Vec<u64> = reports.iter().map(|p| p.sent).collect();
+    sent.dedup();
+    assert!(sent.len() > 2);
+    assert!(sent.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(
+        reports.last().unwrap(),
+        &Progress {
+            sent: 204800,
+            send_total: Some(204800),
+            received: 2,
+            receive_total: Some(2),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_progress_download() {
+    let _mock = mock("GET", "/progress/file").with_body("hello world").create();
+
+    let (reports, callback) = recorder();
+    let body = fetch("file", callback).await.unwrap();
+    assert_eq!(body, b"hello world");
+    {
+        let reports = reports.lock().unwrap();
+        // The total is reported when the response is received, before the body.
+        assert_eq!(reports[0].received, 0);
+        assert_eq!(reports[0].receive_total, Some(11));
+        assert_eq!(reports[0].send_total, None);
+        assert_eq!(reports.last().unwrap().received, 11);
+    }
+
+    let dir = std::env::temp_dir().join("feignhttp_progress");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let (reports, callback) = recorder();
+    let download = download("file", &dir.join("file.txt"), callback).await.unwrap();
+    assert_eq!(download.len, 11);
+    let last = *reports.lock().unwrap().last().unwrap();
+    assert_eq!(last.received, 11);
+    assert_eq!(last.receive_total, Some(11));
+}
@@ -1,4 +1,4 @@
-use feignhttp::util::replace;
+use feignhttp::util::{render_body, replace, resolve_env};
 use std::collections::HashMap;
 
 #[test]
@@ -25,3 +25,37 @@ fn test_config() {
 
     assert_eq!(replace("{timeout}", &map), "3000");
 }
+
+#[test]
+fn test_resolve_env() {
+    std::env::set_var("RESOLVE_ENV_HOST", "localhost");
+
+    assert_eq!(resolve_env("http://${RESOLVE_ENV_HOST}:1234").unwrap(), "http://localhost:1234");
+    assert_eq!(resolve_env("${RESOLVE_ENV_HOST:127.0.0.1}").unwrap(), "localhost");
+    assert_eq!(resolve_env("${RESOLVE_ENV_DNE:http://127.0.0.1}/{id}").unwrap(), "http://127.0.0.1/{id}");
+    assert_eq!(resolve_env("${RESOLVE_ENV_DNE:}").unwrap(), "");
+}
+
+#[test]
+fn test_resolve_env_error() {
+    assert!(resolve_env("${RESOLVE_ENV_DNE}").unwrap_err().is_config_error());
+    assert!(resolve_env("${RESOLVE_ENV_HOST").unwrap_err().is_config_error());
+}
+
+#[test]
+fn test_render_body() {
+    let mut map = HashMap::new();
+    map.insert("name", "ja\"ck\n".to_string());
+    map.insert("id", "1".to_string());
+
+    let json = render_body(r#"{"user":"{name}","id":{id}}"#, &map, "application/json").unwrap();
+    assert_eq!(json, r#"{"user":"ja\"ck\n","id":1}"#);
+
+    let form = render_body("user={name}&id={id}", &map, "application/x-www-form-urlencoded").unwrap();
+    assert_eq!(form, "user=ja%22ck%0A&id=1");
+
+    let text = render_body("{name}", &map, "text/plain; charset=utf-8").unwrap();
+    assert_eq!(text, "ja\"ck\n");
+
+    assert!(render_body("{age}", &map, "text/plain").unwrap_err().is_build_error());
+}
@@ -0,0 +1,283 @@
+#[cfg(feature = "json")]
+mod json {
+    use feignhttp::{get, JsonArray, JsonLines};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    pub struct Event {
+        pub id: i32,
+    }
+
+    #[get("http://localhost:1234/json_lines")]
+    pub async fn watch() -> feignhttp::Result<JsonLines<Event>> {}
+
+    #[get("http://localhost:1234/json_lines_invalid")]
+    pub async fn watch_invalid() -> feignhttp::Result<JsonLines<Event>> {}
+
+    #[get("http://localhost:1234/json_lines_long")]
+    pub async fn watch_long() -> feignhttp::Result<JsonLines<Event>> {}
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    pub struct Report {
+        pub id: i32,
+        pub name: String,
+        pub tags: Vec<String>,
+    }
+
+    #[get("http://localhost:1234/json_array")]
+    pub async fn reports() -> feignhttp::Result<JsonArray<Report>> {}
+
+    #[get("http://localhost:1234/json_array_{name}")]
+    pub async fn reports_invalid(#[path] name: &str) -> feignhttp::Result<JsonArray<Report>> {}
+}
+
+#[tokio::test]
+async fn test_json_lines() {
+    #[cfg(feature = "json")]
+    {
+        use futures_util::StreamExt;
+        use json::*;
+        use mockito::mock;
+
+        let _mock = mock("GET", "/json_lines")
+            .match_header("accept", "application/x-ndjson, application/jsonl, application/json")
+            .with_header("content-type", "application/x-ndjson")
+            .with_body_from_fn(|w| {
+                w.write_all(b"{\"id\":1}\n")?;
+                w.write_all(b"{\"id\"")?;
+                w.write_all(b":2}\r\n\n")?;
+                w.write_all(b"{\"id\":3}")
+            })
+            .create();
+
+        let events: Vec<Event> = watch()
+            .await
+            .unwrap()
+            .map(|event| event.unwrap())
+            .collect()
+            .await;
+        assert_eq!(events, vec![Event { id: 1 }, Event { id: 2 }, Event { id: 3 }]);
+    }
+}
+
+#[tokio::test]
+async fn test_json_lines_error() {
+    #[cfg(feature = "json")]
+    {
+        use futures_util::StreamExt;
+        use json::*;
+        use mockito::mock;
+
+        let _mock_invalid = mock("GET", "/json_lines_invalid")
+            .with_body("{\"id\":1}\n\n{\"id\":\"a\"}\n{\"id\":4}\n")
+            .create();
+        let _mock_long = mock("GET", "/json_lines_long")
+            .with_body("{\"id\":1}\n{\"id\":12345}\n{\"id\":3}\n")
+            .create();
+
+        let events: Vec<_> = watch_invalid().await.unwrap().collect().await;
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].as_ref().unwrap(), &Event { id: 1 });
+        let e = events[1].as_ref().unwrap_err();
+        assert!(e.is_decode_error());
+        assert!(e.to_string().contains("invalid json at line 3"));
+        assert_eq!(events[2].as_ref().unwrap(), &Event { id: 4 });
+
+        // A line longer than the maximum length ends the stream.
+        let events: Vec<_> = watch_long().await.unwrap().max_line_length(10).collect().await;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_ref().unwrap(), &Event { id: 1 });
+        let e = events[1].as_ref().unwrap_err();
+        assert!(e.is_decode_error());
+        assert!(e.to_string().contains("line 2 exceeds the maximum length of 10 bytes"));
+    }
+}
+
+#[tokio::test]
+async fn test_json_array() {
+    #[cfg(feature = "json")]
+    {
+        use futures_util::StreamExt;
+        use json::*;
+        use mockito::mock;
+
+        let _mock = mock("GET", "/json_array")
+            .match_header("accept", "application/json")
+            .with_header("content-type", "application/json")
+            .with_body_from_fn(|w| {
+                w.write_all(b" [ {\"id\": 1, \"name\": \"a],\\\"{\", ")?;
+                w.write_all(b"\"tags\": [\"x\", \"y\"]} ,")?;
+                w.write_all(b"{\"id\":2,\"name\":\"b\",\"tags\":[]}")?;
+                w.write_all(b"]\n")
+            })
+            .create();
+
+        let reports: Vec<Report> = reports().await.unwrap().map(|r| r.unwrap()).collect().await;
+        assert_eq!(
+            reports,
+            vec![
+                Report {
+                    id: 1,
+                    name: "a],\"{".to_string(),
+                    tags: vec!["x".to_string(), "y".to_string()],
+                },
+                Report { id: 2, name: "b".to_string(), tags: vec![] },
+            ]
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_json_array_error() {
+    #[cfg(feature = "json")]
+    {
+        use futures_util::StreamExt;
+        use json::*;
+        use mockito::mock;
+
+        let _mock_empty = mock("GET", "/json_array_empty").with_body("[]").create();
+        let _mock_item = mock("GET", "/json_array_item")
+            .with_body(r#"[{"id":1,"name":"a","tags":[]},{"id":"x"},{"id":3,"name":"c","tags":[]}]"#)
+            .create();
+        let _mock_object = mock("GET", "/json_array_object").with_body(r#"{"id":1}"#).create();
+        let _mock_truncated = mock("GET", "/json_array_truncated")
+            .with_body(r#"[{"id":1,"name":"a","tags":[]},{"id":2"#)
+            .create();
+
+        let items: Vec<_> = reports_invalid("empty").await.unwrap().collect().await;
+        assert!(items.is_empty());
+
+        let items: Vec<_> = reports_invalid("item").await.unwrap().collect().await;
+        assert_eq!(items.len(), 3);
+        assert!(items[1].as_ref().unwrap_err().to_string().contains("invalid json at item 1"));
+        assert_eq!(items[2].as_ref().unwrap().id, 3);
+
+        let items: Vec<_> = reports_invalid("object").await.unwrap().collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].as_ref().unwrap_err().to_string().contains("expected a json array"));
+
+        let items: Vec<_> = reports_invalid("truncated").await.unwrap().collect().await;
+        assert_eq!(items.len(), 2);
+        assert!(items[1].as_ref().unwrap_err().is_decode_error());
+        assert!(items[1].as_ref().unwrap_err().to_string().contains("unexpected end of json array"));
+
+        // An item longer than the maximum length ends the stream.
+        let items: Vec<_> = reports_invalid("item").await.unwrap().max_item_length(16).collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].as_ref().unwrap_err().to_string().contains("item 0 exceeds the maximum length of 16 bytes"));
+    }
+}
+
+mod sse {
+    use feignhttp::{get, EventStream};
+
+    #[get("http://localhost:1234/events")]
+    pub async fn events() -> feignhttp::Result<EventStream> {}
+
+    #[get("http://localhost:1234/events_reconnect")]
+    pub async fn events_reconnect() -> feignhttp::Result<EventStream> {}
+
+    #[cfg(feature = "json")]
+    #[get("http://localhost:1234/events_json")]
+    pub async fn events_json() -> feignhttp::Result<EventStream<feignhttp::Json<super::json::Event>>> {}
+}
+
+#[tokio::test]
+async fn test_event_stream() {
+    use feignhttp::Event;
+    use futures_util::StreamExt;
+    use mockito::mock;
+    use sse::*;
+    use std::time::Duration;
+
+    let _mock = mock("GET", "/events")
+        .match_header("accept", "text/event-stream")
+        .with_header("content-type", "text/event-stream")
+        .with_body(
+            "\u{feff}: comment\n\
+             data: hello\n\n\
+             event: build\n\
+             id: 1\n\
+             retry: 10\n\
+             data: line 1\n\
+             data:line 2\r\n\r\n\
+             id\n\
+             data\n\n\
+             event: ignored\n\n\
+             data: incomplete",
+        )
+        .create();
+
+    let events: Vec<Event> = events()
+        .await
+        .unwrap()
+        .reconnect(false)
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+    assert_eq!(
+        events,
+        vec![
+            Event { event: "message".to_string(), data: "hello".to_string(), id: None, retry: None },
+            Event {
+                event: "build".to_string(),
+                data: "line 1\nline 2".to_string(),
+                id: Some("1".to_string()),
+                retry: Some(Duration::from_millis(10)),
+            },
+            Event { event: "message".to_string(), data: "".to_string(), id: None, retry: None },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_event_stream_reconnect() {
+    use futures_util::StreamExt;
+    use mockito::{mock, Matcher};
+    use sse::*;
+
+    let _mock = mock("GET", "/events_reconnect")
+        .match_header("last-event-id", Matcher::Missing)
+        .with_header("content-type", "text/event-stream")
+        .with_body("retry: 10\nid: 1\ndata: a\n\nid: 2\ndata: b")
+        .create();
+    let _mock_reconnect = mock("GET", "/events_reconnect")
+        .match_header("last-event-id", "1")
+        .with_header("content-type", "text/event-stream")
+        .with_body("id: 3\ndata: c\n\n")
+        .create();
+    let _mock_end = mock("GET", "/events_reconnect")
+        .match_header("last-event-id", "3")
+        .with_status(204)
+        .create();
+
+    let mut stream = events_reconnect().await.unwrap();
+    let mut data = Vec::new();
+    while let Some(event) = stream.next().await {
+        data.push(event.unwrap().data);
+    }
+    // The incomplete event is discarded, and its id is sent when reconnecting.
+    assert_eq!(data, vec!["a", "c"]);
+    assert_eq!(stream.last_event_id(), Some("3"));
+}
+
+#[tokio::test]
+async fn test_event_stream_json() {
+    #[cfg(feature = "json")]
+    {
+        use futures_util::StreamExt;
+        use mockito::mock;
+        use sse::*;
+
+        let _mock = mock("GET", "/events_json")
+            .with_header("content-type", "text/event-stream; charset=utf-8")
+            .with_body("data: {\"id\":1}\n\ndata: {\"id\":\n\ndata: {\"id\":3}\n\n")
+            .create();
+
+        let events: Vec<_> = events_json().await.unwrap().reconnect(false).collect().await;
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].as_ref().unwrap().data.id, 1);
+        assert!(events[1].as_ref().unwrap_err().is_decode_error());
+        assert_eq!(events[2].as_ref().unwrap().data.id, 3);
+    }
+}
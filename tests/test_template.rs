@@ -0,0 +1,121 @@
+use feignhttp::{TemplateValue, UriTemplate};
+use std::collections::HashMap;
+
+fn vars() -> HashMap<&'static str, TemplateValue> {
+    let mut map = HashMap::new();
+    map.insert("var", TemplateValue::Str("value".to_string()));
+    map.insert("hello", TemplateValue::Str("Hello World!".to_string()));
+    map.insert("path", TemplateValue::Str("/foo/bar".to_string()));
+    map.insert("empty", TemplateValue::Str("".to_string()));
+    map.insert("x", TemplateValue::Str("1024".to_string()));
+    map.insert("y", TemplateValue::Str("768".to_string()));
+    map.insert(
+        "list",
+        TemplateValue::List(vec![
+            "red".to_string(),
+            "green".to_string(),
+            "blue".to_string(),
+        ]),
+    );
+    map
+}
+
+fn expand(template: &str) -> String {
+    UriTemplate::parse(template)
+        .expand(&vars(), &HashMap::new())
+        .unwrap()
+}
+
+#[test]
+fn test_simple_expansion() {
+    assert_eq!(expand("{var}"), "value");
+    assert_eq!(expand("{hello}"), "Hello%20World%21");
+    assert_eq!(expand("{path}/here"), "%2Ffoo%2Fbar/here");
+    assert_eq!(expand("map?{x,y}"), "map?1024,768");
+    assert_eq!(expand("{var:3}"), "val");
+    assert_eq!(expand("{list}"), "red,green,blue");
+    assert_eq!(expand("{list*}"), "red,green,blue");
+}
+
+#[test]
+fn test_reserved_expansion() {
+    assert_eq!(expand("{+path}/here"), "/foo/bar/here");
+    assert_eq!(expand("{+hello}"), "Hello%20World!");
+    assert_eq!(expand("{#path}"), "#/foo/bar");
+}
+
+#[test]
+fn test_operator_expansion() {
+    assert_eq!(expand("X{.var}"), "X.value");
+    assert_eq!(expand("{/var,x}/here"), "/value/1024/here");
+    assert_eq!(expand("{/list*}"), "/red/green/blue");
+    assert_eq!(expand("{;x,y,empty}"), ";x=1024;y=768;empty");
+    assert_eq!(expand("{?x,y,empty}"), "?x=1024&y=768&empty=");
+    assert_eq!(expand("?fixed=yes{&x}"), "?fixed=yes&x=1024");
+    assert_eq!(expand("{?list}"), "?list=red,green,blue");
+    assert_eq!(expand("{?list*}"), "?list=red&list=green&list=blue");
+}
+
+#[test]
+fn test_undefined_variable() {
+    assert_eq!(expand("/search{?q,x}"), "/search?x=1024");
+    assert_eq!(expand("/search{?q}"), "/search");
+
+    let err = UriTemplate::parse("/find/{name}")
+        .expand(&vars(), &HashMap::new())
+        .unwrap_err();
+    assert!(err.is_build_error());
+}
+
+#[test]
+fn test_param_value() {
+    let mut params = HashMap::new();
+    params.insert("var", "param".to_string());
+    params.insert("name", "jack".to_string());
+
+    let url = UriTemplate::parse("/{var}/{name}")
+        .expand(&vars(), &params)
+        .unwrap();
+    assert_eq!(url, "/value/jack");
+}
+
+#[test]
+fn test_raw_value() {
+    let mut map = HashMap::new();
+    map.insert("id", TemplateValue::Raw("{name}/a b".to_string()));
+
+    let url = UriTemplate::parse("/find/{id}")
+        .expand(&map, &HashMap::new())
+        .unwrap();
+    assert_eq!(url, "/find/{name}/a b");
+}
+
+#[test]
+fn test_literal_braces() {
+    assert_eq!(expand("/find/{}/{a-b}/{?}"), "/find/{}/{a-b}/{?}");
+    assert_eq!(UriTemplate::parse("/find/{x}").variables(), vec!["x"]);
+}
+
+#[test]
+fn test_dot_segment() {
+    let mut map = HashMap::new();
+    map.insert("id", TemplateValue::Str("..".to_string()));
+    map.insert(
+        "ids",
+        TemplateValue::List(vec!["a".to_string(), ".".to_string()]),
+    );
+
+    let params = HashMap::new();
+    assert!(UriTemplate::parse("/find/{id}")
+        .expand(&map, &params)
+        .is_err());
+    assert!(UriTemplate::parse("/find{/ids*}")
+        .expand(&map, &params)
+        .is_err());
+    assert_eq!(
+        UriTemplate::parse("/find/{id}.txt")
+            .expand(&map, &params)
+            .unwrap(),
+        "/find/...txt"
+    );
+}